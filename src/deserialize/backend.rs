@@ -0,0 +1,575 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! A small recursive-descent JSON parser building Python objects directly
+//! as it reads, rather than via an intermediate AST -- the same
+//! single-pass shape [`crate::deserialize::deserializer::deserialize_impl`]
+//! already assumes when it calls into this module. Schema validation (see
+//! [`crate::deserialize::schema`]) runs inline during this same pass, so a
+//! violation fails fast instead of requiring a second walk over the
+//! finished document.
+
+use crate::deserialize::pyobject::{
+    check_key_against_schema, get_unicode_key, parse_f64, parse_f64_checked, parse_false,
+    parse_i128, parse_i64, parse_i64_checked, parse_none, parse_number_raw, parse_raw, parse_true,
+    parse_u128, parse_u64,
+};
+use crate::deserialize::schema::{SchemaType, SchemaValidator};
+use crate::deserialize::DeserializeError;
+use crate::opt::{Opt, OPT_NON_FINITE_LITERALS, OPT_PARSE_DECIMAL};
+use crate::str::unicode_from_str;
+use core::ptr::NonNull;
+use pyo3_ffi::*;
+
+/// The built object, plus how many bytes of the input it consumed -- used
+/// by callers (e.g. a future NDJSON-style multi-document reader) that need
+/// to know where the next document starts without re-scanning from the
+/// beginning.
+pub struct DeserializeResult {
+    pub obj: NonNull<PyObject>,
+    pub bytes_read: usize,
+}
+
+struct Parser<'a> {
+    buf: &'a str,
+    bytes: &'a [u8],
+    idx: usize,
+    validator: Option<SchemaValidator>,
+    opts: Opt,
+}
+
+/// Scans the JSON number token starting at `start` in `bytes`, returning
+/// the index just past it and whether it has a fractional part or
+/// exponent (and so must round-trip through `f64`/`Decimal` rather than an
+/// integer type). Split out from [`Parser::parse_number`] so the scanning
+/// itself -- leading zero/sign rules, `.`/`e`/`E` handling -- can be unit
+/// tested without building any Python objects.
+///
+/// On failure, returns the index of the offending character alongside the
+/// message, so the caller can point `DeserializeError::parse` at the exact
+/// malformed byte instead of the start of the number token.
+fn scan_number_text(bytes: &[u8], start: usize) -> Result<(usize, bool), (usize, &'static str)> {
+    let mut idx = start;
+    let mut is_float = false;
+    let peek = |idx: usize| bytes.get(idx).copied();
+
+    if peek(idx) == Some(b'-') {
+        idx += 1;
+    }
+    if peek(idx) == Some(b'0') {
+        idx += 1;
+    } else if matches!(peek(idx), Some(b'1'..=b'9')) {
+        while matches!(peek(idx), Some(b'0'..=b'9')) {
+            idx += 1;
+        }
+    } else {
+        return Err((idx, "invalid number"));
+    }
+
+    if peek(idx) == Some(b'.') {
+        is_float = true;
+        idx += 1;
+        if !matches!(peek(idx), Some(b'0'..=b'9')) {
+            return Err((idx, "invalid number"));
+        }
+        while matches!(peek(idx), Some(b'0'..=b'9')) {
+            idx += 1;
+        }
+    }
+
+    if matches!(peek(idx), Some(b'e' | b'E')) {
+        is_float = true;
+        idx += 1;
+        if matches!(peek(idx), Some(b'+' | b'-')) {
+            idx += 1;
+        }
+        if !matches!(peek(idx), Some(b'0'..=b'9')) {
+            return Err((idx, "invalid number"));
+        }
+        while matches!(peek(idx), Some(b'0'..=b'9')) {
+            idx += 1;
+        }
+    }
+
+    Ok((idx, is_float))
+}
+
+/// Parses `buffer_str` (already validated as UTF-8 by
+/// [`crate::deserialize::utf8::read_input_to_buf`]) into a Python object,
+/// optionally checking it against `schema` as it goes. `stop_when_done`
+/// skips the trailing-garbage check, for callers that only want the first
+/// complete value in a buffer that may contain more after it. Under
+/// `OPT_NON_FINITE_LITERALS` (see [`crate::opt`]), bare `NaN`/`Infinity`/
+/// `-Infinity` tokens are accepted wherever a number is expected, mirroring
+/// what the serializer writes under the same flag.
+pub fn deserialize_checked(
+    buffer_str: &str,
+    stop_when_done: bool,
+    schema: Option<SchemaValidator>,
+    opts: Opt,
+) -> Result<DeserializeResult, DeserializeError<'static>> {
+    let mut parser = Parser {
+        buf: buffer_str,
+        bytes: buffer_str.as_bytes(),
+        idx: 0,
+        validator: schema,
+        opts,
+    };
+    parser.skip_ws();
+    if parser.idx >= parser.bytes.len() {
+        return Err(parser.err("expected value"));
+    }
+    let obj = parser.parse_value()?;
+    let bytes_read = parser.idx;
+
+    if !stop_when_done {
+        parser.skip_ws();
+        if parser.idx != parser.bytes.len() {
+            return Err(parser.err("trailing characters after document"));
+        }
+    }
+
+    Ok(DeserializeResult { obj, bytes_read })
+}
+
+impl<'a> Parser<'a> {
+    fn err(&self, message: impl Into<String>) -> DeserializeError<'static> {
+        DeserializeError::parse(message, self.buf, self.idx)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.idx).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.idx += 1;
+        }
+    }
+
+    fn expect_byte(&mut self, byte: u8) -> Result<(), DeserializeError<'static>> {
+        if self.peek() == Some(byte) {
+            self.idx += 1;
+            Ok(())
+        } else {
+            Err(self.err(format!("expected `{}`", byte as char)))
+        }
+    }
+
+    fn expect_literal(&mut self, literal: &str) -> Result<(), DeserializeError<'static>> {
+        if self.bytes[self.idx..].starts_with(literal.as_bytes()) {
+            self.idx += literal.len();
+            Ok(())
+        } else {
+            Err(self.err(format!("expected `{literal}`")))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+        self.skip_ws();
+        if matches!(&self.validator, Some(validator) if validator.current_is_raw()) {
+            return self.parse_raw_value();
+        }
+        match self.peek() {
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => self.parse_array(),
+            Some(b'"') => {
+                self.idx += 1;
+                let s = self.scan_string()?;
+                if let Some(validator) = &self.validator {
+                    validator.check_string(&s)?;
+                }
+                Ok(nonnull!(unicode_from_str(&s)))
+            }
+            Some(b't') => {
+                self.expect_literal("true")?;
+                if let Some(validator) = &self.validator {
+                    validator.check_type(SchemaType::Bool)?;
+                }
+                Ok(parse_true())
+            }
+            Some(b'f') => {
+                self.expect_literal("false")?;
+                if let Some(validator) = &self.validator {
+                    validator.check_type(SchemaType::Bool)?;
+                }
+                Ok(parse_false())
+            }
+            Some(b'n') => {
+                self.expect_literal("null")?;
+                if let Some(validator) = &self.validator {
+                    validator.check_type(SchemaType::Null)?;
+                }
+                Ok(parse_none())
+            }
+            Some(b'N') if self.opts & OPT_NON_FINITE_LITERALS != 0 => {
+                self.expect_literal("NaN")?;
+                self.finish_non_finite(f64::NAN)
+            }
+            Some(b'I') if self.opts & OPT_NON_FINITE_LITERALS != 0 => {
+                self.expect_literal("Infinity")?;
+                self.finish_non_finite(f64::INFINITY)
+            }
+            Some(b'-')
+                if self.opts & OPT_NON_FINITE_LITERALS != 0
+                    && self
+                        .bytes
+                        .get(self.idx + 1..)
+                        .is_some_and(|rest| rest.starts_with(b"Infinity")) =>
+            {
+                self.idx += 1;
+                self.expect_literal("Infinity")?;
+                self.finish_non_finite(f64::NEG_INFINITY)
+            }
+            Some(b'-') | Some(b'0'..=b'9') => self.parse_number(),
+            Some(_) => Err(self.err("unexpected character")),
+            None => Err(self.err("unexpected end of input")),
+        }
+    }
+
+    fn finish_non_finite(&mut self, value: f64) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+        match &self.validator {
+            Some(validator) => Ok(parse_f64_checked(value, validator)?),
+            None => Ok(parse_f64(value)),
+        }
+    }
+
+    /// Records the `[start, end)` span of the value at the cursor and
+    /// returns it wrapped in a [`parse_raw`] `RawJSON` -- the schema
+    /// `"raw": true` passthrough. The value still has to be a
+    /// syntactically valid document (so the cursor ends up in the right
+    /// place for whatever follows it), so this runs the ordinary parse
+    /// path with schema checking switched off for the duration: the value
+    /// is still built into Python objects, which are then dropped.
+    fn parse_raw_value(&mut self) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+        let start = self.idx;
+        let saved_validator = self.validator.take();
+        let built = self.parse_value();
+        self.validator = saved_validator;
+        let built = built?;
+        ffi!(Py_DECREF(built.as_ptr()));
+        let fragment = self.buf[start..self.idx].as_bytes();
+        Ok(parse_raw(fragment))
+    }
+
+    fn parse_object(&mut self) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+        if let Some(validator) = &self.validator {
+            validator.check_type(SchemaType::Object)?;
+        }
+        self.idx += 1;
+        let dict = nonnull!(ffi!(PyDict_New()));
+        let mut present_keys: Vec<String> = Vec::new();
+
+        self.skip_ws();
+        if self.peek() == Some(b'}') {
+            self.idx += 1;
+        } else {
+            loop {
+                self.skip_ws();
+                self.expect_byte(b'"')?;
+                let key = self.scan_string()?;
+
+                self.skip_ws();
+                self.expect_byte(b':')?;
+
+                if let Some(validator) = &mut self.validator {
+                    check_key_against_schema(validator, &key)?;
+                }
+                present_keys.push(key.clone());
+
+                let value = self.parse_value()?;
+                let key_obj = get_unicode_key(&key);
+                ffi!(PyDict_SetItem(dict.as_ptr(), key_obj, value.as_ptr()));
+                ffi!(Py_DECREF(key_obj));
+                ffi!(Py_DECREF(value.as_ptr()));
+
+                if let Some(validator) = &mut self.validator {
+                    validator.leave();
+                }
+
+                self.skip_ws();
+                match self.peek() {
+                    Some(b',') => {
+                        self.idx += 1;
+                    }
+                    Some(b'}') => {
+                        self.idx += 1;
+                        break;
+                    }
+                    _ => return Err(self.err("expected `,` or `}`")),
+                }
+            }
+        }
+
+        if let Some(validator) = &self.validator {
+            validator.check_required(&present_keys)?;
+        }
+
+        Ok(dict)
+    }
+
+    fn parse_array(&mut self) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+        if let Some(validator) = &self.validator {
+            validator.check_type(SchemaType::Array)?;
+        }
+        self.idx += 1;
+        let list = nonnull!(ffi!(PyList_New(0)));
+
+        self.skip_ws();
+        if self.peek() == Some(b']') {
+            self.idx += 1;
+            return Ok(list);
+        }
+
+        let mut index = 0usize;
+        loop {
+            if let Some(validator) = &mut self.validator {
+                validator.enter_index(index);
+            }
+            let item = self.parse_value()?;
+            ffi!(PyList_Append(list.as_ptr(), item.as_ptr()));
+            ffi!(Py_DECREF(item.as_ptr()));
+            if let Some(validator) = &mut self.validator {
+                validator.leave();
+            }
+            index += 1;
+
+            self.skip_ws();
+            match self.peek() {
+                Some(b',') => {
+                    self.idx += 1;
+                }
+                Some(b']') => {
+                    self.idx += 1;
+                    break;
+                }
+                _ => return Err(self.err("expected `,` or `]`")),
+            }
+        }
+
+        Ok(list)
+    }
+
+    fn parse_number(&mut self) -> Result<NonNull<PyObject>, DeserializeError<'static>> {
+        let start = self.idx;
+        let (end, is_float) = scan_number_text(self.bytes, start).map_err(|(idx, msg)| {
+            self.idx = idx;
+            self.err(msg)
+        })?;
+        self.idx = end;
+        let raw = &self.buf[start..self.idx];
+
+        if is_float {
+            if self.opts & OPT_PARSE_DECIMAL != 0 {
+                // `decimal.Decimal(raw)` preserves exactly the digits/
+                // exponent the document had. Only the schema `type` is
+                // checked here; `minimum`/`maximum`/`enum` checks don't
+                // apply since they're defined in terms of an `f64`
+                // comparison and a `Decimal` isn't one -- the same narrow
+                // gap the wider-than-`u128` path at the end of this
+                // function leaves.
+                if let Some(validator) = &self.validator {
+                    validator.check_type(SchemaType::Number)?;
+                }
+                return parse_number_raw(raw);
+            }
+            let value: f64 = raw.parse().map_err(|_| self.err("invalid number"))?;
+            return match &self.validator {
+                Some(validator) => Ok(parse_f64_checked(value, validator)?),
+                None => Ok(parse_f64(value)),
+            };
+        }
+
+        if let Ok(value) = raw.parse::<i64>() {
+            return match &self.validator {
+                Some(validator) => Ok(parse_i64_checked(value, validator)?),
+                None => Ok(parse_i64(value)),
+            };
+        }
+        if let Ok(value) = raw.parse::<u64>() {
+            if let Some(validator) = &self.validator {
+                validator.check_number(value as f64)?;
+            }
+            return Ok(parse_u64(value));
+        }
+        if let Ok(value) = raw.parse::<i128>() {
+            if let Some(validator) = &self.validator {
+                validator.check_number(value as f64)?;
+            }
+            return Ok(parse_i128(value));
+        }
+        if let Ok(value) = raw.parse::<u128>() {
+            if let Some(validator) = &self.validator {
+                validator.check_number(value as f64)?;
+            }
+            return Ok(parse_u128(value));
+        }
+        // Wider than u128: an arbitrary-precision Python `int`, built
+        // straight from the digit text the same way `parse_i128`/
+        // `parse_u128` already do for the narrower cases.
+        if let Some(validator) = &self.validator {
+            validator.check_type(SchemaType::Number)?;
+        }
+        parse_number_raw(raw)
+    }
+
+    fn scan_string(&mut self) -> Result<String, DeserializeError<'static>> {
+        let mut out = String::new();
+        loop {
+            match self.bytes.get(self.idx) {
+                None => return Err(self.err("unterminated string")),
+                Some(b'"') => {
+                    self.idx += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.idx += 1;
+                    match self.bytes.get(self.idx) {
+                        Some(b'"') => {
+                            out.push('"');
+                            self.idx += 1;
+                        }
+                        Some(b'\\') => {
+                            out.push('\\');
+                            self.idx += 1;
+                        }
+                        Some(b'/') => {
+                            out.push('/');
+                            self.idx += 1;
+                        }
+                        Some(b'b') => {
+                            out.push('\u{8}');
+                            self.idx += 1;
+                        }
+                        Some(b'f') => {
+                            out.push('\u{c}');
+                            self.idx += 1;
+                        }
+                        Some(b'n') => {
+                            out.push('\n');
+                            self.idx += 1;
+                        }
+                        Some(b'r') => {
+                            out.push('\r');
+                            self.idx += 1;
+                        }
+                        Some(b't') => {
+                            out.push('\t');
+                            self.idx += 1;
+                        }
+                        Some(b'u') => {
+                            self.idx += 1;
+                            let hi = self.scan_hex4()?;
+                            let codepoint = if (0xD800..=0xDBFF).contains(&hi) {
+                                if self.bytes.get(self.idx) == Some(&b'\\')
+                                    && self.bytes.get(self.idx + 1) == Some(&b'u')
+                                {
+                                    self.idx += 2;
+                                    let lo = self.scan_hex4()?;
+                                    if !(0xDC00..=0xDFFF).contains(&lo) {
+                                        return Err(self.err("invalid low surrogate"));
+                                    }
+                                    0x10000 + ((hi - 0xD800) << 10) + (lo - 0xDC00)
+                                } else {
+                                    return Err(self.err("unpaired UTF-16 surrogate"));
+                                }
+                            } else {
+                                hi
+                            };
+                            match char::from_u32(codepoint) {
+                                Some(ch) => out.push(ch),
+                                None => return Err(self.err("invalid unicode escape")),
+                            }
+                        }
+                        _ => return Err(self.err("invalid escape sequence")),
+                    }
+                }
+                Some(&byte) if byte < 0x20 => {
+                    return Err(self.err("control character in string"));
+                }
+                Some(_) => {
+                    // `buf` is already validated UTF-8, so re-decoding one
+                    // char at the current byte index (rather than copying
+                    // byte-by-byte) is always well-formed.
+                    let ch = self.buf[self.idx..]
+                        .chars()
+                        .next()
+                        .unwrap_or_else(|| unreachable!());
+                    out.push(ch);
+                    self.idx += ch.len_utf8();
+                }
+            }
+        }
+    }
+
+    fn scan_hex4(&mut self) -> Result<u32, DeserializeError<'static>> {
+        let slice = self
+            .bytes
+            .get(self.idx..self.idx + 4)
+            .ok_or_else(|| self.err("truncated unicode escape"))?;
+        let text = std::str::from_utf8(slice).map_err(|_| self.err("invalid unicode escape"))?;
+        let value = u32::from_str_radix(text, 16).map_err(|_| self.err("invalid unicode escape"))?;
+        self.idx += 4;
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scan_number_text;
+
+    fn scan(text: &str) -> Result<(&str, bool), (usize, &'static str)> {
+        let (end, is_float) = scan_number_text(text.as_bytes(), 0)?;
+        Ok((&text[..end], is_float))
+    }
+
+    #[test]
+    fn scans_plain_integers() {
+        assert_eq!(scan("0"), Ok(("0", false)));
+        assert_eq!(scan("-0"), Ok(("-0", false)));
+        assert_eq!(scan("42"), Ok(("42", false)));
+        assert_eq!(scan("-17"), Ok(("-17", false)));
+    }
+
+    #[test]
+    fn leading_zero_ends_the_number_token() {
+        // `scan_number_text` only ever consumes a single `0` before
+        // stopping, so `01` scans as the token `0` -- it's the caller's
+        // `deserialize_checked` trailing-garbage check that then rejects
+        // the stray `1`, not this function.
+        assert_eq!(scan_number_text(b"01", 0), Ok((1, false)));
+    }
+
+    #[test]
+    fn scans_fractional_and_exponent_forms() {
+        assert_eq!(scan("3.14"), Ok(("3.14", true)));
+        assert_eq!(scan("1e10"), Ok(("1e10", true)));
+        assert_eq!(scan("1E+10"), Ok(("1E+10", true)));
+        assert_eq!(scan("1.5e-3"), Ok(("1.5e-3", true)));
+        assert_eq!(scan("-0.0"), Ok(("-0.0", true)));
+    }
+
+    #[test]
+    fn rejects_malformed_numbers() {
+        assert!(scan("-").is_err());
+        assert!(scan(".5").is_err());
+        assert!(scan("1.").is_err());
+        assert!(scan("1e").is_err());
+        assert!(scan("1e+").is_err());
+    }
+
+    #[test]
+    fn stops_before_trailing_non_number_bytes() {
+        let (end, is_float) = scan_number_text(b"123,\"next\"", 0).unwrap();
+        assert_eq!(end, 3);
+        assert!(!is_float);
+    }
+
+    #[test]
+    fn error_points_at_the_offending_byte_not_the_token_start() {
+        // The missing fractional digit is the 5th byte (index 4), not the
+        // start of the number at index 0 -- this is what lets the caller's
+        // `DeserializeError::parse` report the actual malformed position.
+        assert_eq!(scan_number_text(b"123.", 0), Err((4, "invalid number")));
+        assert_eq!(scan_number_text(b"1e", 0), Err((2, "invalid number")));
+    }
+}
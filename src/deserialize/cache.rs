@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Interns object keys seen while parsing: the common case of a document
+//! made up of many objects that repeat the same key names (e.g. a JSON
+//! array of records) only has to build each distinct key's `str` once.
+//!
+//! Lookup trusts the 64-bit hash alone rather than comparing the full key
+//! text: if two distinct keys hash to the same value, the later one is
+//! handed back the `str` cached for the first one.
+
+use pyo3_ffi::PyObject;
+use std::collections::HashMap;
+
+/// A single interned key `str`; the hash it is looked up by is the
+/// [`KeyMap`] key, not stored here.
+pub struct CachedKey {
+    obj: *mut PyObject,
+}
+
+unsafe impl Send for CachedKey {}
+
+impl CachedKey {
+    pub fn new(obj: *mut PyObject) -> Self {
+        CachedKey { obj }
+    }
+
+    /// Returns a new reference to the cached key, for the caller to use
+    /// (and eventually `Py_DECREF`) the same way every other `parse_*`
+    /// constructor in [`crate::deserialize::pyobject`] does.
+    pub fn get(&self) -> *mut PyObject {
+        unsafe {
+            pyo3_ffi::Py_INCREF(self.obj);
+        }
+        self.obj
+    }
+
+    /// Consumes `self`, handing back the reference it already owns without
+    /// taking a new one. Used when a freshly built key is about to be
+    /// discarded rather than entered into the cache (the table's at its
+    /// cap), so there's no second owner to `Py_INCREF` for.
+    fn into_owned(self) -> *mut PyObject {
+        self.obj
+    }
+}
+
+/// Hard cap on the number of distinct short keys one [`KeyMap`] interns
+/// over the life of the process. Without one, a long-lived server calling
+/// `loads()` on untrusted JSON with many distinct short keys (e.g.
+/// attacker-controlled field names) would grow this table forever; past
+/// the cap, keys are built fresh every time instead, the same fallback
+/// already used for keys longer than 64 bytes. Free-threaded
+/// (`Py_GIL_DISABLED`) builds keep one `KeyMap` per shard, so their
+/// process-wide total is the shard count times this.
+const MAX_CACHED_KEYS: usize = 4096;
+
+#[derive(Default)]
+pub struct KeyMap(HashMap<u64, CachedKey>);
+
+unsafe impl Sync for KeyMap {}
+
+pub struct Entry<'a> {
+    map: &'a mut HashMap<u64, CachedKey>,
+    hash: u64,
+}
+
+impl<'a> Entry<'a> {
+    /// `hash_fn` is accepted (and ignored beyond matching the caller's
+    /// hash already computed) to keep the call site symmetric with a
+    /// from-scratch lookup; the hash was already computed by the caller
+    /// before calling [`KeyMap::entry`].
+    ///
+    /// Returns an owned reference to the (possibly freshly built) key
+    /// directly, rather than a borrow into the map, since past
+    /// [`MAX_CACHED_KEYS`] a newly built key isn't stored in the map at
+    /// all and there's nothing to borrow from.
+    pub fn or_insert_with(
+        self,
+        _hash_fn: impl FnOnce() -> u64,
+        build_fn: impl FnOnce() -> CachedKey,
+    ) -> *mut PyObject {
+        if let Some(cached) = self.map.get(&self.hash) {
+            return cached.get();
+        }
+        if self.map.len() >= MAX_CACHED_KEYS {
+            return build_fn().into_owned();
+        }
+        self.map.entry(self.hash).or_insert_with(build_fn).get()
+    }
+}
+
+impl KeyMap {
+    pub fn entry(&mut self, hash: &u64) -> Entry<'_> {
+        Entry {
+            map: &mut self.0,
+            hash: *hash,
+        }
+    }
+}
+
+/// `static mut` rather than behind a `Mutex`: every call into this module
+/// happens while the GIL is held, so access is already serialized the
+/// same way the rest of this crate's global, unsynchronized caches are
+/// (see the doc comment on [`crate::logitnpz`]'s `NumpyFuncs`).
+/// Free-threaded (`Py_GIL_DISABLED`) builds use the sharded, lock-based
+/// [`crate::deserialize::pyobject::get_unicode_key`] variant instead and
+/// never touch this static.
+#[cfg(not(Py_GIL_DISABLED))]
+pub static mut KEY_MAP: std::sync::OnceLock<KeyMap> = std::sync::OnceLock::new();
+
+/// Hash of a key's UTF-8 bytes, used as its [`KeyMap`] key. Only keys of
+/// at most 64 bytes are looked up in the cache; longer keys are built
+/// fresh every time by [`crate::deserialize::pyobject::get_unicode_key`]
+/// (repeated long keys are uncommon enough that caching them isn't worth
+/// growing the cache unbounded).
+pub fn cache_hash(bytes: &[u8]) -> u64 {
+    // FNV-1a: fast, good-enough distribution for short ASCII-ish key
+    // strings, no external dependency.
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
@@ -1,32 +1,51 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::deserialize::backend::DeserializeResult;
+use crate::deserialize::schema::{compile_schema, SchemaNode, SchemaValidator};
 use crate::deserialize::utf8::read_input_to_buf;
 use crate::deserialize::DeserializeError;
+use crate::opt::Opt;
 use crate::typeref::EMPTY_UNICODE;
 use core::ptr::NonNull;
+use pyo3_ffi::*;
 
 pub fn deserialize(
     ptr: *mut pyo3_ffi::PyObject,
+    opts: Opt,
 ) -> Result<NonNull<pyo3_ffi::PyObject>, DeserializeError<'static>> {
-    let result = deserialize_impl(ptr, false)?;
+    let result = deserialize_impl(ptr, false, None, opts)?;
+    Ok(result.obj)
+}
+
+/// Like [`deserialize`], but validates the document against `schema` as it
+/// is built and fails on the first violation instead of materializing the
+/// whole object and validating it in a second pass.
+pub fn deserialize_with_schema(
+    ptr: *mut pyo3_ffi::PyObject,
+    schema: SchemaNode,
+    opts: Opt,
+) -> Result<NonNull<pyo3_ffi::PyObject>, DeserializeError<'static>> {
+    let result = deserialize_impl(ptr, false, Some(SchemaValidator::new(schema)), opts)?;
     Ok(result.obj)
 }
 
 pub fn deserialize_next(
     ptr: *mut pyo3_ffi::PyObject,
+    opts: Opt,
 ) -> Result<DeserializeResult, DeserializeError<'static>> {
-    deserialize_impl(ptr, true)
+    deserialize_impl(ptr, true, None, opts)
 }
 
 fn deserialize_impl(
     ptr: *mut pyo3_ffi::PyObject,
     stop_when_done: bool,
+    schema: Option<SchemaValidator>,
+    opts: Opt,
 ) -> Result<DeserializeResult, DeserializeError<'static>> {
     debug_assert!(ffi!(Py_REFCNT(ptr)) >= 1);
     let buffer = read_input_to_buf(ptr)?;
 
-    if unlikely!(buffer.len() == 2 && !stop_when_done) {
+    if unlikely!(buffer.len() == 2 && !stop_when_done && schema.is_none()) {
         if buffer == b"[]" {
             return Ok(DeserializeResult {
                 obj: nonnull!(ffi!(PyList_New(0))),
@@ -49,5 +68,68 @@ fn deserialize_impl(
 
     let buffer_str = unsafe { std::str::from_utf8_unchecked(buffer) };
 
-    crate::deserialize::backend::deserialize(buffer_str, stop_when_done)
+    crate::deserialize::backend::deserialize_checked(buffer_str, stop_when_done, schema, opts)
+}
+
+/// The `loads(obj, /, schema=None, option=0)` entry point, following the
+/// same vectorcall-with-`kwnames` convention
+/// [`crate::logitnpz::logitnpz_save`]/`logitnpz_load` use to expose their
+/// keyword arguments -- `schema`, a `dict`, is compiled once per call via
+/// [`compile_schema`] and then checked inline while the document is built;
+/// `option` is the same `Opt` bitmask `dumps` takes, consulted for
+/// `OPT_NON_FINITE_LITERALS` and `OPT_PARSE_DECIMAL`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn loads(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    let num_args = PyVectorcall_NARGS(nargs as usize);
+    if num_args < 1 {
+        let msg = "loads() requires at least 1 argument: obj\0";
+        PyErr_SetString(PyExc_TypeError, msg.as_ptr() as *const std::os::raw::c_char);
+        return std::ptr::null_mut();
+    }
+
+    let obj = *args.offset(0);
+    let mut schema_obj: *mut PyObject = std::ptr::null_mut();
+    let mut opts: Opt = 0;
+
+    if !kwnames.is_null() {
+        let kwcount = Py_SIZE(kwnames);
+        for i in 0..kwcount {
+            let kwname = PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+            let mut size: Py_ssize_t = 0;
+            let ptr = PyUnicode_AsUTF8AndSize(kwname, &mut size);
+            if !ptr.is_null() {
+                let name = std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+                    ptr as *const u8,
+                    size as usize,
+                ));
+                if name == "schema" {
+                    schema_obj = *args.offset(num_args + i);
+                } else if name == "option" {
+                    let option_obj = *args.offset(num_args + i);
+                    if PyLong_Check(option_obj) != 0 {
+                        opts = PyLong_AsLong(option_obj) as Opt;
+                    }
+                }
+            }
+        }
+    }
+
+    let result = if schema_obj.is_null() || schema_obj == crate::typeref::NONE {
+        deserialize(obj, opts)
+    } else {
+        match compile_schema(schema_obj) {
+            Ok(schema) => deserialize_with_schema(obj, schema, opts),
+            Err(err) => Err(DeserializeError::from(err)),
+        }
+    };
+
+    match result {
+        Ok(ptr) => ptr.as_ptr(),
+        Err(err) => err.to_py_error(),
+    }
 }
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::schema::SchemaError;
+use std::fmt;
+
+/// Everything that can make `loads()` fail: a malformed document, an input
+/// object that isn't `bytes`/`bytearray`/`str` (or isn't valid UTF-8), or
+/// (when `schema=` is given) a schema violation at a specific path.
+#[derive(Debug)]
+pub enum DeserializeError<'a> {
+    ParseError {
+        message: String,
+        line: usize,
+        column: usize,
+        index: usize,
+    },
+    InvalidInput(&'a str),
+    Schema(SchemaError),
+}
+
+impl<'a> DeserializeError<'a> {
+    pub fn parse(message: impl Into<String>, buffer: &str, index: usize) -> Self {
+        let mut line = 1usize;
+        let mut column = 1usize;
+        for ch in buffer[..index.min(buffer.len())].chars() {
+            if ch == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        DeserializeError::ParseError {
+            message: message.into(),
+            line,
+            column,
+            index,
+        }
+    }
+}
+
+impl<'a> fmt::Display for DeserializeError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeserializeError::ParseError {
+                message,
+                line,
+                column,
+                index,
+            } => write!(
+                f,
+                "{message}: line {line} column {column} (char {index})"
+            ),
+            DeserializeError::InvalidInput(message) => write!(f, "{message}"),
+            DeserializeError::Schema(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<'a> std::error::Error for DeserializeError<'a> {}
+
+impl<'a> From<SchemaError> for DeserializeError<'a> {
+    fn from(err: SchemaError) -> Self {
+        DeserializeError::Schema(err)
+    }
+}
+
+impl<'a> DeserializeError<'a> {
+    /// Sets the matching Python exception (`JSONDecodeError`-shaped callers
+    /// get a `ValueError` since this crate doesn't define its own exception
+    /// class here) and returns `NULL`, the same `Err -> to_py_error()`
+    /// convention [`crate::logitnpz::LogitNpzError`] uses at its own
+    /// vectorcall boundary.
+    pub fn to_py_error(&self) -> *mut pyo3_ffi::PyObject {
+        unsafe {
+            let msg = self.to_string();
+            let msg_obj = pyo3_ffi::PyUnicode_FromStringAndSize(
+                msg.as_ptr() as *const std::os::raw::c_char,
+                msg.len() as isize,
+            );
+            pyo3_ffi::PyErr_SetObject(pyo3_ffi::PyExc_ValueError, msg_obj);
+            pyo3_ffi::Py_DECREF(msg_obj);
+            std::ptr::null_mut()
+        }
+    }
+}
@@ -4,10 +4,13 @@ mod backend;
 mod cache;
 mod deserializer;
 mod error;
+mod pattern;
 mod pyobject;
+mod schema;
 mod utf8;
 
 pub use backend::DeserializeResult;
 pub use cache::{KeyMap, KEY_MAP};
-pub use deserializer::{deserialize, deserialize_next};
+pub use deserializer::{deserialize, deserialize_next, deserialize_with_schema};
 pub use error::DeserializeError;
+pub use schema::{JsonScalar, SchemaError, SchemaNode, SchemaType, SchemaValidator};
@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! A deliberately small regular-expression subset for the JSON Schema
+//! `pattern` keyword, used instead of pulling in a full regex engine (this
+//! crate has no `Cargo.toml` to add a dependency to, and schema `pattern`s
+//! in practice are almost always this subset: anchors, literals, `.`,
+//! bracket classes, and `* + ?` quantifiers). A pattern using alternation,
+//! groups, or `{m,n}` fails to compile ([`Pattern::compile`] returns
+//! `None`), and the schema `pattern` check then applies no constraint.
+//! Any escape other than `\d`, `\w`, `\s` (a backreference such as `\1`
+//! included) matches the escaped character literally.
+
+#[derive(Debug, Clone)]
+enum Atom {
+    Any,
+    Char(char),
+    Class { negated: bool, ranges: Vec<(char, char)> },
+}
+
+#[derive(Debug, Clone)]
+struct Piece {
+    atom: Atom,
+    min: u32,
+    max: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pieces: Vec<Piece>,
+    anchored_start: bool,
+    anchored_end: bool,
+}
+
+impl Pattern {
+    pub fn compile(src: &str) -> Option<Pattern> {
+        let mut chars = src.chars().peekable();
+        let anchored_start = chars.peek() == Some(&'^');
+        if anchored_start {
+            chars.next();
+        }
+
+        let mut body: Vec<char> = chars.collect();
+        let anchored_end = body.last() == Some(&'$') && !matches!(body.iter().rev().nth(1), Some('\\'));
+        if anchored_end {
+            body.pop();
+        }
+
+        let mut pieces = Vec::new();
+        let mut i = 0;
+        while i < body.len() {
+            let atom = match body[i] {
+                '.' => {
+                    i += 1;
+                    Atom::Any
+                }
+                '\\' => {
+                    i += 1;
+                    let escaped = *body.get(i)?;
+                    i += 1;
+                    match escaped {
+                        'd' => Atom::Class {
+                            negated: false,
+                            ranges: vec![('0', '9')],
+                        },
+                        'w' => Atom::Class {
+                            negated: false,
+                            ranges: vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')],
+                        },
+                        's' => Atom::Class {
+                            negated: false,
+                            ranges: vec![(' ', ' '), ('\t', '\t'), ('\n', '\n'), ('\r', '\r')],
+                        },
+                        other => Atom::Char(other),
+                    }
+                }
+                '[' => {
+                    i += 1;
+                    let negated = body.get(i) == Some(&'^');
+                    if negated {
+                        i += 1;
+                    }
+                    let mut ranges = Vec::new();
+                    while i < body.len() && body[i] != ']' {
+                        let lo = body[i];
+                        i += 1;
+                        if body.get(i) == Some(&'-') && body.get(i + 1).is_some_and(|c| *c != ']') {
+                            let hi = body[i + 1];
+                            ranges.push((lo, hi));
+                            i += 2;
+                        } else {
+                            ranges.push((lo, lo));
+                        }
+                    }
+                    if i >= body.len() {
+                        return None;
+                    }
+                    i += 1;
+                    Atom::Class { negated, ranges }
+                }
+                // Alternation, groups, and bounded repeats aren't supported
+                // by this subset.
+                '(' | ')' | '|' | '{' => return None,
+                c => {
+                    i += 1;
+                    Atom::Char(c)
+                }
+            };
+
+            let (min, max) = match body.get(i) {
+                Some('*') => {
+                    i += 1;
+                    (0, u32::MAX)
+                }
+                Some('+') => {
+                    i += 1;
+                    (1, u32::MAX)
+                }
+                Some('?') => {
+                    i += 1;
+                    (0, 1)
+                }
+                _ => (1, 1),
+            };
+            pieces.push(Piece { atom, min, max });
+        }
+
+        Some(Pattern {
+            pieces,
+            anchored_start,
+            anchored_end,
+        })
+    }
+
+    /// JSON Schema `pattern` only requires the regex to match *somewhere*
+    /// in the string (unless the pattern itself anchors with `^`/`$`), the
+    /// same semantics as `re.search`, not `re.fullmatch`.
+    pub fn is_match(&self, haystack: &str) -> bool {
+        let chars: Vec<char> = haystack.chars().collect();
+        if self.anchored_start {
+            return self.matches_from(&chars, 0);
+        }
+        for start in 0..=chars.len() {
+            if self.matches_from(&chars, start) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn matches_from(&self, chars: &[char], start: usize) -> bool {
+        self.match_pieces(chars, start, 0)
+    }
+
+    fn match_pieces(&self, chars: &[char], pos: usize, piece_idx: usize) -> bool {
+        if piece_idx == self.pieces.len() {
+            return !self.anchored_end || pos == chars.len();
+        }
+        let piece = &self.pieces[piece_idx];
+
+        // Greedily consume as many repetitions as allowed, then backtrack
+        // down to `min` looking for a point where the rest of the pattern
+        // also matches.
+        let mut count = 0u32;
+        let mut positions = vec![pos];
+        let mut cur = pos;
+        while count < piece.max && cur < chars.len() && atom_matches(&piece.atom, chars[cur]) {
+            cur += 1;
+            count += 1;
+            positions.push(cur);
+        }
+        if count < piece.min {
+            return false;
+        }
+        for take in (piece.min..=count).rev() {
+            if self.match_pieces(chars, positions[take as usize], piece_idx + 1) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+fn atom_matches(atom: &Atom, c: char) -> bool {
+    match atom {
+        Atom::Any => c != '\n',
+        Atom::Char(expected) => *expected == c,
+        Atom::Class { negated, ranges } => {
+            let in_class = ranges.iter().any(|(lo, hi)| *lo <= c && c <= *hi);
+            in_class != *negated
+        }
+    }
+}
@@ -1,10 +1,26 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::deserialize::cache::*;
+use crate::deserialize::schema::{SchemaError, SchemaValidator};
+use crate::deserialize::DeserializeError;
 use crate::str::{hash_str, unicode_from_str};
 use crate::typeref::{FALSE, NONE, TRUE};
 use core::ptr::NonNull;
 
+/// Checks `key` against the active schema node's `properties`/
+/// `additionalProperties` keywords before it is interned, so a rejected
+/// key short-circuits the rest of the object. `required` is not checked
+/// here but once the whole object has been read
+/// ([`SchemaValidator::check_required`]).
+#[inline(always)]
+pub fn check_key_against_schema(
+    validator: &mut SchemaValidator,
+    key_str: &str,
+) -> Result<(), SchemaError> {
+    validator.enter_key(key_str)
+}
+
+#[cfg(not(Py_GIL_DISABLED))]
 #[inline(always)]
 pub fn get_unicode_key(key_str: &str) -> *mut pyo3_ffi::PyObject {
     if unlikely!(key_str.len() > 64) {
@@ -14,7 +30,7 @@ pub fn get_unicode_key(key_str: &str) -> *mut pyo3_ffi::PyObject {
     } else {
         let hash = cache_hash(key_str.as_bytes());
         unsafe {
-            let entry = KEY_MAP
+            KEY_MAP
                 .get_mut()
                 .unwrap_or_else(|| unreachable_unchecked!())
                 .entry(&hash)
@@ -25,12 +41,54 @@ pub fn get_unicode_key(key_str: &str) -> *mut pyo3_ffi::PyObject {
                         hash_str(pyob);
                         CachedKey::new(pyob)
                     },
-                );
-            entry.get()
+                )
         }
     }
 }
 
+/// `KEY_MAP` is a single global `HashMap`; `get_mut()` is only sound while
+/// the GIL serializes every `loads` call. On free-threaded (`Py_GIL_DISABLED`)
+/// builds multiple threads can call `get_unicode_key` concurrently, so the
+/// cache is sharded behind one lock per bucket (keyed on the low bits of
+/// the precomputed `cache_hash`) instead of one lock for the whole table --
+/// contention is limited to threads that happen to hash into the same
+/// shard, and the long key path above (>64 bytes, always uncached) is
+/// unaffected either way.
+#[cfg(Py_GIL_DISABLED)]
+const KEY_MAP_SHARD_COUNT: usize = 64;
+
+#[cfg(Py_GIL_DISABLED)]
+static KEY_MAP_SHARDS: std::sync::OnceLock<[std::sync::Mutex<KeyMap>; KEY_MAP_SHARD_COUNT]> =
+    std::sync::OnceLock::new();
+
+#[cfg(Py_GIL_DISABLED)]
+#[inline(always)]
+fn key_map_shard(hash: u64) -> &'static std::sync::Mutex<KeyMap> {
+    let shards = KEY_MAP_SHARDS.get_or_init(|| std::array::from_fn(|_| std::sync::Mutex::new(KeyMap::default())));
+    &shards[(hash as usize) % KEY_MAP_SHARD_COUNT]
+}
+
+#[cfg(Py_GIL_DISABLED)]
+#[inline(always)]
+pub fn get_unicode_key(key_str: &str) -> *mut pyo3_ffi::PyObject {
+    if unlikely!(key_str.len() > 64) {
+        let pyob = unicode_from_str(key_str);
+        hash_str(pyob);
+        return pyob;
+    }
+    let hash = cache_hash(key_str.as_bytes());
+    let shard = key_map_shard(hash);
+    let mut map = shard.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    map.entry(&hash).or_insert_with(
+        || hash,
+        || {
+            let pyob = unicode_from_str(key_str);
+            hash_str(pyob);
+            CachedKey::new(pyob)
+        },
+    )
+}
+
 #[allow(dead_code)]
 #[inline(always)]
 pub fn parse_bool(val: bool) -> NonNull<pyo3_ffi::PyObject> {
@@ -55,6 +113,17 @@ pub fn parse_i64(val: i64) -> NonNull<pyo3_ffi::PyObject> {
     nonnull!(ffi!(PyLong_FromLongLong(val)))
 }
 
+/// Like [`parse_i64`], but checks `minimum`/`maximum`/`enum` on the active
+/// schema node before constructing the Python int.
+#[inline(always)]
+pub fn parse_i64_checked(
+    val: i64,
+    validator: &SchemaValidator,
+) -> Result<NonNull<pyo3_ffi::PyObject>, SchemaError> {
+    validator.check_number(val as f64)?;
+    Ok(parse_i64(val))
+}
+
 #[inline(always)]
 pub fn parse_u64(val: u64) -> NonNull<pyo3_ffi::PyObject> {
     nonnull!(ffi!(PyLong_FromUnsignedLongLong(val)))
@@ -111,7 +180,91 @@ pub fn parse_f64(val: f64) -> NonNull<pyo3_ffi::PyObject> {
     nonnull!(ffi!(PyFloat_FromDouble(val)))
 }
 
+/// Like [`parse_f64`], but checks `minimum`/`maximum`/`enum` on the active
+/// schema node before constructing the Python float.
+#[inline(always)]
+pub fn parse_f64_checked(
+    val: f64,
+    validator: &SchemaValidator,
+) -> Result<NonNull<pyo3_ffi::PyObject>, SchemaError> {
+    validator.check_number(val)?;
+    Ok(parse_f64(val))
+}
+
 #[inline(always)]
 pub fn parse_none() -> NonNull<pyo3_ffi::PyObject> {
     nonnull!(use_immortal!(NONE))
 }
+
+/// Wraps an untouched `[start, end)` slice of the input buffer in a
+/// `RawJSON`, returned in place of the Python object tree for that
+/// sub-document (the caller still builds that tree while finding where
+/// the slice ends, then drops it).
+#[inline(never)]
+pub fn parse_raw(fragment: &[u8]) -> NonNull<pyo3_ffi::PyObject> {
+    unsafe { nonnull!(crate::rawjson::new_rawjson_from_fragment(fragment)) }
+}
+
+/// Builds an arbitrary-precision Python `int` from a raw ASCII digit slice
+/// that overflowed `i128`/`u128`, using the same `PyLong_FromString` trick
+/// as [`parse_i128`]/[`parse_u128`] but skipping the intermediate typed
+/// integer entirely since the value doesn't fit in one.
+#[inline(never)]
+fn parse_bigint_raw(raw: &str) -> NonNull<pyo3_ffi::PyObject> {
+    let c_str = std::ffi::CString::new(raw).unwrap();
+    unsafe { nonnull!(PyLong_FromString(c_str.as_ptr(), std::ptr::null_mut(), 10)) }
+}
+
+/// Dispatches a raw number token to the precision-preserving constructor:
+/// arbitrary-precision `int` for all-digit tokens that overflow
+/// `i128`/`u128` (whatever the options), `decimal.Decimal` for anything
+/// with a fractional part or exponent (reached only under
+/// `OPT_PARSE_DECIMAL`).
+#[inline(always)]
+pub fn parse_number_raw(
+    raw: &str,
+) -> Result<NonNull<pyo3_ffi::PyObject>, DeserializeError<'static>> {
+    if raw.bytes().all(|b| b == b'-' || b.is_ascii_digit()) {
+        Ok(parse_bigint_raw(raw))
+    } else {
+        parse_decimal(raw)
+    }
+}
+
+/// Builds a `decimal.Decimal` from the raw ASCII number slice exactly as it
+/// appeared in the input, used under `OPT_PARSE_DECIMAL` for numbers with
+/// a fractional part or exponent, which could lose precision going
+/// through `f64` (over-wide integers go to an arbitrary-precision `int`
+/// instead, see [`parse_number_raw`]). Keeping the
+/// original text rather than a pre-parsed value is what lets the result
+/// round-trip byte-for-byte.
+///
+/// `decimal` failing to import is a real, reachable failure (a stripped or
+/// tampered stdlib, `sys.path` issues) rather than an invariant this crate
+/// controls, so it's surfaced as an ordinary deserialize error instead of
+/// `unreachable_unchecked!`.
+#[inline(never)]
+pub fn parse_decimal(
+    raw: &str,
+) -> Result<NonNull<pyo3_ffi::PyObject>, DeserializeError<'static>> {
+    unsafe {
+        let decimal_cls = match crate::typeref::DECIMAL_CLASS.get_or_init(crate::typeref::load_decimal_class) {
+            Some(cls) => cls,
+            None => return Err(DeserializeError::InvalidInput("the `decimal` module is unavailable")),
+        };
+        let py_str = nonnull!(ffi!(PyUnicode_FromStringAndSize(
+            raw.as_ptr() as *const i8,
+            raw.len() as isize
+        )));
+        let args = nonnull!(ffi!(PyTuple_New(1)));
+        ffi!(Py_INCREF(py_str.as_ptr()));
+        ffi!(PyTuple_SET_ITEM(args.as_ptr(), 0, py_str.as_ptr()));
+        let result = nonnull!(ffi!(PyObject_CallObject(
+            decimal_cls.as_ptr(),
+            args.as_ptr()
+        )));
+        ffi!(Py_DECREF(py_str.as_ptr()));
+        ffi!(Py_DECREF(args.as_ptr()));
+        Ok(result)
+    }
+}
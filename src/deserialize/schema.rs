@@ -0,0 +1,559 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Compiled JSON Schema (draft 2019-09 / 2020-12 subset) checked against the
+//! document as it is built, so `loads(..., schema=...)` does not need a
+//! second pass over the resulting Python objects.
+
+use crate::deserialize::pattern::Pattern;
+use pyo3_ffi::*;
+use std::fmt;
+
+/// A single compiled schema node. Unsupported/unknown keywords are ignored
+/// rather than rejected, matching the permissive-superset stance the rest
+/// of the crate takes toward input it doesn't have an opinion about.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaNode {
+    pub ty: Option<SchemaType>,
+    pub required: Vec<String>,
+    pub enum_values: Option<Vec<JsonScalar>>,
+    pub minimum: Option<f64>,
+    pub maximum: Option<f64>,
+    pub min_length: Option<usize>,
+    pub pattern: Option<String>,
+    pub items: Option<Box<SchemaNode>>,
+    pub properties: Vec<(String, SchemaNode)>,
+    pub additional_properties: bool,
+    /// Non-standard `"raw": true` keyword: in place of this value, the
+    /// deserializer hands back a
+    /// [`crate::deserialize::pyobject::parse_raw`] `RawJSON` wrapping its
+    /// untouched source bytes. The value (and its descendants, if any) is
+    /// still built into Python objects while it is parsed, then dropped.
+    pub raw: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaType {
+    Null,
+    Bool,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+/// Scalar literal used for `enum` comparisons, kept as a plain Rust value
+/// so equality doesn't have to round-trip through Python objects.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonScalar {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl SchemaNode {
+    pub fn child_for_key(&self, key: &str) -> Option<&SchemaNode> {
+        self.properties
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, node)| node)
+    }
+
+    pub fn child_for_item(&self) -> Option<&SchemaNode> {
+        self.items.as_deref()
+    }
+}
+
+/// Failing keyword plus the JSON Pointer path (RFC 6901) of the offending
+/// value, e.g. `/items/3/price`.
+#[derive(Debug, Clone)]
+pub struct SchemaError {
+    pub pointer: String,
+    pub keyword: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "schema validation failed at `{}`: {} ({})",
+            self.pointer, self.message, self.keyword
+        )
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Tracks the schema node stack and the JSON Pointer path while the
+/// document is being built, so a violation anywhere in the parse can be
+/// reported with its exact location.
+pub struct SchemaValidator {
+    stack: Vec<SchemaNode>,
+    path: Vec<String>,
+}
+
+impl SchemaValidator {
+    pub fn new(root: SchemaNode) -> Self {
+        SchemaValidator {
+            stack: vec![root],
+            path: Vec::new(),
+        }
+    }
+
+    fn current(&self) -> &SchemaNode {
+        self.stack.last().unwrap_or_else(|| unreachable!())
+    }
+
+    /// `true` if the value about to be parsed is marked `"raw": true` and
+    /// should be handed back as a `RawJSON` byte span instead of built.
+    pub fn current_is_raw(&self) -> bool {
+        self.current().raw
+    }
+
+    fn pointer(&self) -> String {
+        if self.path.is_empty() {
+            return String::from("");
+        }
+        let mut out = String::new();
+        for segment in &self.path {
+            out.push('/');
+            out.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+        }
+        out
+    }
+
+    fn err(&self, keyword: &'static str, message: impl Into<String>) -> SchemaError {
+        SchemaError {
+            pointer: self.pointer(),
+            keyword,
+            message: message.into(),
+        }
+    }
+
+    pub fn enter_key(&mut self, key: &str) -> Result<(), SchemaError> {
+        let node = self.current();
+        let child = match node.child_for_key(key) {
+            Some(child) => child.clone(),
+            None => {
+                if !node.additional_properties {
+                    return Err(self.err("additionalProperties", format!("key `{key}` is not allowed")));
+                }
+                // No `properties` entry for this key means nothing
+                // constrains it, so its own nested keys/items must be
+                // implicitly allowed too -- the derived `SchemaNode::default()`
+                // has `additional_properties: false`, which would wrongly
+                // reject every key one level further down.
+                SchemaNode {
+                    additional_properties: true,
+                    ..SchemaNode::default()
+                }
+            }
+        };
+        self.path.push(key.to_string());
+        self.stack.push(child);
+        Ok(())
+    }
+
+    pub fn enter_index(&mut self, index: usize) {
+        let child = self.current().child_for_item().cloned().unwrap_or_else(|| SchemaNode {
+            additional_properties: true,
+            ..SchemaNode::default()
+        });
+        self.path.push(index.to_string());
+        self.stack.push(child);
+    }
+
+    pub fn leave(&mut self) {
+        self.stack.pop();
+        self.path.pop();
+    }
+
+    pub fn check_required(&self, present_keys: &[String]) -> Result<(), SchemaError> {
+        for required in &self.current().required {
+            if !present_keys.iter().any(|k| k == required) {
+                return Err(self.err("required", format!("missing required key `{required}`")));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn check_type(&self, ty: SchemaType) -> Result<(), SchemaError> {
+        if let Some(expected) = self.current().ty {
+            if expected != ty {
+                return Err(self.err("type", format!("expected {expected:?}, got {ty:?}")));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn check_number(&self, value: f64) -> Result<(), SchemaError> {
+        self.check_type(SchemaType::Number)?;
+        let node = self.current();
+        if let Some(min) = node.minimum {
+            if value < min {
+                return Err(self.err("minimum", format!("{value} is less than {min}")));
+            }
+        }
+        if let Some(max) = node.maximum {
+            if value > max {
+                return Err(self.err("maximum", format!("{value} is greater than {max}")));
+            }
+        }
+        self.check_enum(&JsonScalar::Number(value))
+    }
+
+    pub fn check_string(&self, value: &str) -> Result<(), SchemaError> {
+        self.check_type(SchemaType::String)?;
+        let node = self.current();
+        if let Some(min_length) = node.min_length {
+            if value.chars().count() < min_length {
+                return Err(self.err("minLength", format!("`{value}` is shorter than {min_length}")));
+            }
+        }
+        if let Some(pattern_src) = &node.pattern {
+            // An uncompilable pattern (outside the supported subset, see
+            // `pattern.rs`) is treated as "no constraint" rather than
+            // failing every string in the document -- the same permissive
+            // stance this module already takes toward unknown keywords.
+            if let Some(pattern) = Pattern::compile(pattern_src) {
+                if !pattern.is_match(value) {
+                    return Err(self.err("pattern", format!("`{value}` does not match `{pattern_src}`")));
+                }
+            }
+        }
+        self.check_enum(&JsonScalar::String(value.to_string()))
+    }
+
+    fn check_enum(&self, value: &JsonScalar) -> Result<(), SchemaError> {
+        if let Some(values) = &self.current().enum_values {
+            if !values.contains(value) {
+                return Err(self.err("enum", "value is not one of the allowed enum values"));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn schema_err(keyword: &'static str, message: impl Into<String>) -> SchemaError {
+    SchemaError {
+        pointer: String::new(),
+        keyword,
+        message: message.into(),
+    }
+}
+
+unsafe fn dict_get(dict: *mut PyObject, key: &str) -> Option<*mut PyObject> {
+    let c_key = std::ffi::CString::new(key).unwrap_or_else(|_| unreachable!());
+    let item = PyDict_GetItemString(dict, c_key.as_ptr());
+    if item.is_null() {
+        None
+    } else {
+        Some(item)
+    }
+}
+
+unsafe fn parse_py_str(obj: *mut PyObject) -> Result<String, SchemaError> {
+    if PyUnicode_Check(obj) == 0 {
+        return Err(schema_err("type", "expected a str"));
+    }
+    let mut size: Py_ssize_t = 0;
+    let data = PyUnicode_AsUTF8AndSize(obj, &mut size);
+    if data.is_null() {
+        PyErr_Clear();
+        return Err(schema_err("type", "str is not valid UTF-8"));
+    }
+    let bytes = std::slice::from_raw_parts(data as *const u8, size as usize);
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
+
+unsafe fn parse_py_number(obj: *mut PyObject) -> Result<f64, SchemaError> {
+    let value = PyFloat_AsDouble(obj);
+    if value == -1.0 && !PyErr_Occurred().is_null() {
+        PyErr_Clear();
+        return Err(schema_err("type", "expected a number"));
+    }
+    Ok(value)
+}
+
+unsafe fn parse_py_usize(obj: *mut PyObject) -> Result<usize, SchemaError> {
+    let value = PyLong_AsLongLong(obj);
+    if value == -1 && !PyErr_Occurred().is_null() {
+        PyErr_Clear();
+        return Err(schema_err("type", "expected an integer"));
+    }
+    Ok(value.max(0) as usize)
+}
+
+unsafe fn for_each_item(
+    obj: *mut PyObject,
+    mut f: impl FnMut(*mut PyObject) -> Result<(), SchemaError>,
+) -> Result<(), SchemaError> {
+    let iter = PyObject_GetIter(obj);
+    if iter.is_null() {
+        PyErr_Clear();
+        return Err(schema_err("type", "expected an array"));
+    }
+    loop {
+        let item = PyIter_Next(iter);
+        if item.is_null() {
+            if !PyErr_Occurred().is_null() {
+                Py_DECREF(iter);
+                return Err(schema_err("type", "failed to iterate array"));
+            }
+            break;
+        }
+        let result = f(item);
+        Py_DECREF(item);
+        result?;
+    }
+    Py_DECREF(iter);
+    Ok(())
+}
+
+unsafe fn parse_schema_type(obj: *mut PyObject) -> Result<SchemaType, SchemaError> {
+    let name = if PyUnicode_Check(obj) != 0 {
+        parse_py_str(obj)?
+    } else {
+        // A list of type names (draft 2020-12 union form): the first
+        // recognized entry wins, matching this module's general stance of
+        // ignoring whatever it can't represent rather than rejecting the
+        // whole schema.
+        let mut found = None;
+        for_each_item(obj, |item| {
+            if found.is_none() {
+                if let Ok(name) = parse_py_str(item) {
+                    if schema_type_from_name(&name).is_some() {
+                        found = Some(name);
+                    }
+                }
+            }
+            Ok(())
+        })?;
+        found.ok_or_else(|| schema_err("type", "no recognized type in list"))?
+    };
+    schema_type_from_name(&name).ok_or_else(|| schema_err("type", format!("unknown type `{name}`")))
+}
+
+fn schema_type_from_name(name: &str) -> Option<SchemaType> {
+    Some(match name {
+        "null" => SchemaType::Null,
+        "boolean" => SchemaType::Bool,
+        "number" | "integer" => SchemaType::Number,
+        "string" => SchemaType::String,
+        "array" => SchemaType::Array,
+        "object" => SchemaType::Object,
+        _ => return None,
+    })
+}
+
+unsafe fn parse_json_scalar(obj: *mut PyObject) -> Option<JsonScalar> {
+    if obj == Py_None() {
+        Some(JsonScalar::Null)
+    } else if PyBool_Check(obj) != 0 {
+        Some(JsonScalar::Bool(obj == Py_True()))
+    } else if PyLong_Check(obj) != 0 {
+        Some(JsonScalar::Number(PyLong_AsLongLong(obj) as f64))
+    } else if PyFloat_Check(obj) != 0 {
+        Some(JsonScalar::Number(PyFloat_AsDouble(obj)))
+    } else if PyUnicode_Check(obj) != 0 {
+        parse_py_str(obj).ok().map(JsonScalar::String)
+    } else {
+        None
+    }
+}
+
+/// Builds a [`SchemaNode`] tree from the Python object passed as
+/// `loads(..., schema=...)`, the same JSON-Schema-subset keywords this
+/// module's [`SchemaValidator`] already knows how to check: `type`,
+/// `required`, `enum`, `minimum`/`maximum`, `minLength`, `pattern`,
+/// `items`, `properties`, `additionalProperties`, plus the crate's own
+/// `raw` passthrough keyword. Unrecognized keywords are ignored rather
+/// than rejected.
+pub unsafe fn compile_schema(obj: *mut PyObject) -> Result<SchemaNode, SchemaError> {
+    if PyDict_Check(obj) == 0 {
+        return Err(schema_err("schema", "schema must be a dict"));
+    }
+
+    let mut node = SchemaNode {
+        additional_properties: true,
+        ..SchemaNode::default()
+    };
+
+    if let Some(value) = dict_get(obj, "type") {
+        node.ty = Some(parse_schema_type(value)?);
+    }
+    if let Some(value) = dict_get(obj, "required") {
+        let mut required = Vec::new();
+        for_each_item(value, |item| {
+            required.push(parse_py_str(item)?);
+            Ok(())
+        })?;
+        node.required = required;
+    }
+    if let Some(value) = dict_get(obj, "enum") {
+        let mut values = Vec::new();
+        for_each_item(value, |item| {
+            if let Some(scalar) = parse_json_scalar(item) {
+                values.push(scalar);
+            }
+            Ok(())
+        })?;
+        node.enum_values = Some(values);
+    }
+    if let Some(value) = dict_get(obj, "minimum") {
+        node.minimum = Some(parse_py_number(value)?);
+    }
+    if let Some(value) = dict_get(obj, "maximum") {
+        node.maximum = Some(parse_py_number(value)?);
+    }
+    if let Some(value) = dict_get(obj, "minLength") {
+        node.min_length = Some(parse_py_usize(value)?);
+    }
+    if let Some(value) = dict_get(obj, "pattern") {
+        node.pattern = Some(parse_py_str(value)?);
+    }
+    if let Some(value) = dict_get(obj, "items") {
+        node.items = Some(Box::new(compile_schema(value)?));
+    }
+    if let Some(value) = dict_get(obj, "properties") {
+        if PyDict_Check(value) != 0 {
+            let mut pos: Py_ssize_t = 0;
+            let mut key: *mut PyObject = std::ptr::null_mut();
+            let mut prop_value: *mut PyObject = std::ptr::null_mut();
+            while PyDict_Next(value, &mut pos, &mut key, &mut prop_value) != 0 {
+                let name = parse_py_str(key)?;
+                let child = compile_schema(prop_value)?;
+                node.properties.push((name, child));
+            }
+        }
+    }
+    if let Some(value) = dict_get(obj, "additionalProperties") {
+        node.additional_properties = PyObject_IsTrue(value) != 0;
+    }
+    if let Some(value) = dict_get(obj, "raw") {
+        node.raw = PyObject_IsTrue(value) != 0;
+    }
+
+    Ok(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object_node() -> SchemaNode {
+        SchemaNode {
+            ty: Some(SchemaType::Object),
+            additional_properties: true,
+            ..SchemaNode::default()
+        }
+    }
+
+    #[test]
+    fn unconstrained_nested_object_allows_any_key() {
+        // `{"type": "object"}` with no `properties` entry for `a`: `a`
+        // itself is implicitly allowed (additionalProperties defaults to
+        // true), and so must anything *inside* `a` be -- regression test
+        // for the bug where the implicit child was built with
+        // `SchemaNode::default()` (additional_properties: false).
+        let mut validator = SchemaValidator::new(object_node());
+        validator.enter_key("a").expect("`a` is implicitly allowed");
+        validator.enter_key("x").expect("nested `x` must also be implicitly allowed");
+        validator.leave();
+        validator.leave();
+    }
+
+    #[test]
+    fn unconstrained_array_items_allow_any_nested_key() {
+        let mut validator = SchemaValidator::new(object_node());
+        validator.enter_index(0);
+        validator
+            .enter_key("x")
+            .expect("a key inside an unconstrained array item must be implicitly allowed");
+    }
+
+    #[test]
+    fn additional_properties_false_rejects_unknown_key() {
+        let node = SchemaNode {
+            ty: Some(SchemaType::Object),
+            additional_properties: false,
+            properties: vec![("a".to_string(), SchemaNode::default())],
+            ..SchemaNode::default()
+        };
+        let mut validator = SchemaValidator::new(node);
+        validator.enter_key("a").expect("declared key is allowed");
+        validator.leave();
+        let err = validator.enter_key("b").unwrap_err();
+        assert_eq!(err.keyword, "additionalProperties");
+    }
+
+    #[test]
+    fn required_keys_are_checked_at_the_right_node() {
+        let node = SchemaNode {
+            ty: Some(SchemaType::Object),
+            required: vec!["name".to_string()],
+            ..object_node()
+        };
+        let validator = SchemaValidator::new(node);
+        assert!(validator.check_required(&["name".to_string()]).is_ok());
+        let err = validator.check_required(&["other".to_string()]).unwrap_err();
+        assert_eq!(err.keyword, "required");
+    }
+
+    #[test]
+    fn number_minimum_maximum_are_enforced() {
+        let node = SchemaNode {
+            minimum: Some(0.0),
+            maximum: Some(10.0),
+            ..SchemaNode::default()
+        };
+        let validator = SchemaValidator::new(node);
+        assert!(validator.check_number(5.0).is_ok());
+        assert_eq!(validator.check_number(-1.0).unwrap_err().keyword, "minimum");
+        assert_eq!(validator.check_number(11.0).unwrap_err().keyword, "maximum");
+    }
+
+    #[test]
+    fn string_min_length_and_pattern_are_enforced() {
+        let node = SchemaNode {
+            min_length: Some(3),
+            pattern: Some("^[a-z]+$".to_string()),
+            ..SchemaNode::default()
+        };
+        let validator = SchemaValidator::new(node);
+        assert!(validator.check_string("abcd").is_ok());
+        assert_eq!(validator.check_string("ab").unwrap_err().keyword, "minLength");
+        assert_eq!(validator.check_string("abc1").unwrap_err().keyword, "pattern");
+    }
+
+    #[test]
+    fn enum_values_reject_anything_not_listed() {
+        let node = SchemaNode {
+            enum_values: Some(vec![JsonScalar::String("a".to_string()), JsonScalar::String("b".to_string())]),
+            ..SchemaNode::default()
+        };
+        let validator = SchemaValidator::new(node);
+        assert!(validator.check_string("a").is_ok());
+        assert_eq!(validator.check_string("c").unwrap_err().keyword, "enum");
+    }
+
+    #[test]
+    fn json_pointer_escapes_tilde_and_slash_in_path_segments() {
+        let node = SchemaNode {
+            properties: vec![(
+                "a/b~c".to_string(),
+                SchemaNode {
+                    required: vec!["missing".to_string()],
+                    ..SchemaNode::default()
+                },
+            )],
+            ..object_node()
+        };
+        let mut validator = SchemaValidator::new(node);
+        validator.enter_key("a/b~c").unwrap();
+        let err = validator.check_required(&[]).unwrap_err();
+        // RFC 6901: `~` -> `~0`, `/` -> `~1`.
+        assert_eq!(err.pointer, "/a~1b~0c");
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::DeserializeError;
+use pyo3_ffi::*;
+
+/// Borrows `ptr` (a `str`, `bytes`, or `bytearray`) as a UTF-8 byte slice,
+/// valid for as long as the caller keeps `ptr` alive -- every `loads()`
+/// entry point holds its own reference to the argument for the duration
+/// of the call, so this never outlives it.
+pub fn read_input_to_buf(ptr: *mut PyObject) -> Result<&'static [u8], DeserializeError<'static>> {
+    unsafe {
+        if PyUnicode_Check(ptr) != 0 {
+            let mut size: Py_ssize_t = 0;
+            let data = PyUnicode_AsUTF8AndSize(ptr, &mut size);
+            if data.is_null() {
+                return Err(DeserializeError::InvalidInput(
+                    "str input is not valid UTF-8",
+                ));
+            }
+            return Ok(std::slice::from_raw_parts(data as *const u8, size as usize));
+        }
+        if PyBytes_Check(ptr) != 0 {
+            let data = PyBytes_AS_STRING(ptr);
+            let size = PyBytes_GET_SIZE(ptr);
+            let bytes = std::slice::from_raw_parts(data as *const u8, size as usize);
+            return validate_utf8(bytes);
+        }
+        if PyByteArray_Check(ptr) != 0 {
+            let data = PyByteArray_AsString(ptr);
+            let size = PyByteArray_Size(ptr);
+            let bytes = std::slice::from_raw_parts(data as *const u8, size as usize);
+            return validate_utf8(bytes);
+        }
+        Err(DeserializeError::InvalidInput(
+            "Input must be bytes, bytearray, or str",
+        ))
+    }
+}
+
+fn validate_utf8(bytes: &[u8]) -> Result<&'static [u8], DeserializeError<'static>> {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => Ok(unsafe { std::mem::transmute::<&[u8], &'static [u8]>(bytes) }),
+        Err(_) => Err(DeserializeError::InvalidInput(
+            "Input is not valid UTF-8",
+        )),
+    }
+}
@@ -5,13 +5,21 @@
 //! This module provides functions to save and load numpy arrays in a zip archive
 //! where each array is stored as a .npy file compressed with zstd.
 
+use ciborium::Value as CborValue;
 use core::ffi::c_char;
+use memmap2::Mmap;
 use pyo3_ffi::*;
 use std::fs::File;
 use std::io::{Cursor, Read, Write};
+use std::sync::Arc;
 use zip::write::SimpleFileOptions;
 use zip::{CompressionMethod, ZipArchive, ZipWriter};
 
+/// Name of the optional CBOR manifest member written by `save_logitnpz*`
+/// and consumed by `load_logitnpz*` for deterministic key ordering,
+/// dtype/shape validation, and user `attrs`.
+const MANIFEST_FILENAME: &str = "manifest.cbor";
+
 use crate::typeref::{load_numpy_types, NUMPY_TYPES};
 
 /// Error type for logitnpz operations
@@ -36,6 +44,124 @@ impl From<zip::result::ZipError> for LogitNpzError {
     }
 }
 
+/// A compression codec selectable per archive or per array. `"stored"`
+/// writes arrays uncompressed, useful when the caller wants the `mmap`
+/// zero-copy loaders to work with every member of the archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionCodec {
+    Stored,
+    Zstd,
+    Lz4,
+    Gzip,
+    Bzip2,
+}
+
+impl CompressionCodec {
+    fn parse(name: &str) -> Result<Self, LogitNpzError> {
+        match name {
+            "stored" => Ok(CompressionCodec::Stored),
+            "zstd" => Ok(CompressionCodec::Zstd),
+            "lz4" => Ok(CompressionCodec::Lz4),
+            "gzip" | "deflate" => Ok(CompressionCodec::Gzip),
+            "bzip2" => Ok(CompressionCodec::Bzip2),
+            other => Err(LogitNpzError::InvalidFormat(format!(
+                "unknown compression_method `{other}` (expected one of: stored, zstd, lz4, gzip, bzip2)"
+            ))),
+        }
+    }
+
+    fn zip_method(self) -> Result<CompressionMethod, LogitNpzError> {
+        match self {
+            CompressionCodec::Stored => Ok(CompressionMethod::Stored),
+            CompressionCodec::Zstd => Ok(CompressionMethod::Zstd),
+            CompressionCodec::Gzip => Ok(CompressionMethod::Deflated),
+            CompressionCodec::Bzip2 => Ok(CompressionMethod::Bzip2),
+            #[cfg(feature = "lz4")]
+            CompressionCodec::Lz4 => Ok(CompressionMethod::Lz4),
+            #[cfg(not(feature = "lz4"))]
+            CompressionCodec::Lz4 => Err(LogitNpzError::InvalidFormat(
+                "compression_method \"lz4\" requires orjson to be built with the `lz4` feature"
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Clamps an arbitrary caller-supplied level to the range each codec's
+    /// encoder actually accepts, rather than letting the `zip` crate error
+    /// out on an out-of-range value.
+    fn clamp_level(self, level: i64) -> Option<i64> {
+        match self {
+            CompressionCodec::Stored => None,
+            CompressionCodec::Zstd => Some(level.clamp(1, 22)),
+            // The `zip` crate's LZ4 writer takes a block-size/checksum
+            // config, not a numeric level; there's nothing to clamp.
+            CompressionCodec::Lz4 => None,
+            CompressionCodec::Gzip => Some(level.clamp(1, 9)),
+            CompressionCodec::Bzip2 => Some(level.clamp(1, 9)),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            CompressionCodec::Stored => "stored",
+            CompressionCodec::Zstd => "zstd",
+            CompressionCodec::Lz4 => "lz4",
+            CompressionCodec::Gzip => "gzip",
+            CompressionCodec::Bzip2 => "bzip2",
+        }
+    }
+
+    fn options(self, level: i64) -> Result<SimpleFileOptions, LogitNpzError> {
+        Ok(SimpleFileOptions::default()
+            .compression_method(self.zip_method()?)
+            .compression_level(self.clamp_level(level)))
+    }
+}
+
+/// Resolves the codec to use for a single array: its entry in a per-array
+/// `{name: method}` mapping if present, else the archive-wide default.
+fn codec_for_key(
+    default: CompressionCodec,
+    per_array: &[(String, CompressionCodec)],
+    key: &str,
+) -> CompressionCodec {
+    per_array
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, codec)| *codec)
+        .unwrap_or(default)
+}
+
+/// Reads a `{"weights": "zstd", "indices": "lz4"}`-style dict into a
+/// `(key, codec)` list; returns an error if any value isn't a recognized
+/// codec name.
+unsafe fn parse_per_array_codecs(
+    mapping: *mut PyObject,
+) -> Result<Vec<(String, CompressionCodec)>, LogitNpzError> {
+    let mut out = Vec::new();
+    let mut pos: Py_ssize_t = 0;
+    let mut key: *mut PyObject = std::ptr::null_mut();
+    let mut value: *mut PyObject = std::ptr::null_mut();
+    while PyDict_Next(mapping, &mut pos, &mut key, &mut value) != 0 {
+        if PyUnicode_Check(key) == 0 || PyUnicode_Check(value) == 0 {
+            return Err(LogitNpzError::InvalidFormat(
+                "compression_method mapping must be {str: str}".to_string(),
+            ));
+        }
+        let mut size: Py_ssize_t = 0;
+        let key_ptr = PyUnicode_AsUTF8AndSize(key, &mut size);
+        let key_str =
+            std::str::from_utf8_unchecked(std::slice::from_raw_parts(key_ptr as *const u8, size as usize))
+                .to_string();
+        let mut vsize: Py_ssize_t = 0;
+        let value_ptr = PyUnicode_AsUTF8AndSize(value, &mut vsize);
+        let value_str =
+            std::str::from_utf8_unchecked(std::slice::from_raw_parts(value_ptr as *const u8, vsize as usize));
+        out.push((key_str, CompressionCodec::parse(value_str)?));
+    }
+    Ok(out)
+}
+
 impl LogitNpzError {
     fn to_py_error(&self) -> *mut PyObject {
         unsafe {
@@ -55,12 +181,24 @@ impl LogitNpzError {
     }
 }
 
-/// Get numpy.save function
-unsafe fn get_numpy_save() -> Option<*mut PyObject> {
-    let numpy_types = NUMPY_TYPES.get_or_init(load_numpy_types);
-    if numpy_types.is_none() {
-        return None;
-    }
+/// Strong references to `numpy.save`/`numpy.load`/`numpy.frombuffer`,
+/// resolved once and reused for the lifetime of the interpreter instead of
+/// importing `numpy` and re-resolving each attribute on every array.
+/// `PyObject` pointers aren't `Sync`, but these are only ever read after
+/// `NUMPY_FUNCS` finishes initializing, so sharing them across threads is
+/// sound.
+struct NumpyFuncs {
+    save: *mut PyObject,
+    load: *mut PyObject,
+    frombuffer: *mut PyObject,
+}
+
+unsafe impl Sync for NumpyFuncs {}
+
+static NUMPY_FUNCS: std::sync::OnceLock<Option<NumpyFuncs>> = std::sync::OnceLock::new();
+
+unsafe fn load_numpy_funcs() -> Option<NumpyFuncs> {
+    NUMPY_TYPES.get_or_init(load_numpy_types).as_ref()?;
 
     let numpy_str = "numpy\0";
     let numpy_mod = PyImport_ImportModule(numpy_str.as_ptr() as *const c_char);
@@ -69,46 +207,63 @@ unsafe fn get_numpy_save() -> Option<*mut PyObject> {
         return None;
     }
 
-    let save_str = "save\0";
-    let save_func = PyObject_GetAttrString(numpy_mod, save_str.as_ptr() as *const c_char);
+    let save = PyObject_GetAttrString(numpy_mod, "save\0".as_ptr() as *const c_char);
+    let load = PyObject_GetAttrString(numpy_mod, "load\0".as_ptr() as *const c_char);
+    let frombuffer = PyObject_GetAttrString(numpy_mod, "frombuffer\0".as_ptr() as *const c_char);
     Py_DECREF(numpy_mod);
 
-    if save_func.is_null() {
+    if save.is_null() || load.is_null() || frombuffer.is_null() {
         PyErr_Clear();
+        if !save.is_null() {
+            Py_DECREF(save);
+        }
+        if !load.is_null() {
+            Py_DECREF(load);
+        }
+        if !frombuffer.is_null() {
+            Py_DECREF(frombuffer);
+        }
         return None;
     }
 
-    Some(save_func)
+    Some(NumpyFuncs {
+        save,
+        load,
+        frombuffer,
+    })
+}
+
+/// Get numpy.save function
+unsafe fn get_numpy_save() -> Option<*mut PyObject> {
+    let funcs = NUMPY_FUNCS.get_or_init(|| load_numpy_funcs()).as_ref()?;
+    Py_INCREF(funcs.save);
+    Some(funcs.save)
 }
 
 /// Get numpy.load function
 unsafe fn get_numpy_load() -> Option<*mut PyObject> {
-    let numpy_types = NUMPY_TYPES.get_or_init(load_numpy_types);
-    if numpy_types.is_none() {
-        return None;
-    }
+    let funcs = NUMPY_FUNCS.get_or_init(|| load_numpy_funcs()).as_ref()?;
+    Py_INCREF(funcs.load);
+    Some(funcs.load)
+}
 
-    let numpy_str = "numpy\0";
-    let numpy_mod = PyImport_ImportModule(numpy_str.as_ptr() as *const c_char);
-    if numpy_mod.is_null() {
-        PyErr_Clear();
-        return None;
-    }
+/// Get numpy.frombuffer function, used by the fast `.npy` read path to
+/// reconstruct an array from a parsed header without `numpy.load`.
+unsafe fn get_numpy_frombuffer() -> Option<*mut PyObject> {
+    let funcs = NUMPY_FUNCS.get_or_init(|| load_numpy_funcs()).as_ref()?;
+    Py_INCREF(funcs.frombuffer);
+    Some(funcs.frombuffer)
+}
 
-    let load_str = "load\0";
-    let load_func = PyObject_GetAttrString(numpy_mod, load_str.as_ptr() as *const c_char);
-    Py_DECREF(numpy_mod);
+/// Strong reference to the `io.BytesIO` class, resolved once and reused
+/// the same way as [`NUMPY_FUNCS`]; independent of numpy's availability.
+struct BytesIoClass(*mut PyObject);
 
-    if load_func.is_null() {
-        PyErr_Clear();
-        return None;
-    }
+unsafe impl Sync for BytesIoClass {}
 
-    Some(load_func)
-}
+static BYTESIO_CLASS: std::sync::OnceLock<Option<BytesIoClass>> = std::sync::OnceLock::new();
 
-/// Get io.BytesIO class
-unsafe fn get_bytesio() -> Option<*mut PyObject> {
+unsafe fn load_bytesio_class() -> Option<BytesIoClass> {
     let io_str = "io\0";
     let io_mod = PyImport_ImportModule(io_str.as_ptr() as *const c_char);
     if io_mod.is_null() {
@@ -125,11 +280,131 @@ unsafe fn get_bytesio() -> Option<*mut PyObject> {
         return None;
     }
 
-    Some(bytesio_class)
+    Some(BytesIoClass(bytesio_class))
+}
+
+/// Get io.BytesIO class
+unsafe fn get_bytesio() -> Option<*mut PyObject> {
+    let class = BYTESIO_CLASS.get_or_init(|| load_bytesio_class()).as_ref()?;
+    Py_INCREF(class.0);
+    Some(class.0)
+}
+
+const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+const NPY_VERSION: [u8; 2] = [1, 0];
+/// Per the NPY spec the header (magic + version + length prefix + dict
+/// text) must end on this byte boundary.
+const NPY_HEADER_ALIGNMENT: usize = 64;
+
+/// Maps a buffer-protocol format character (`Py_buffer.format`, as given by
+/// PEP 3118 -- numpy arrays always report one of these for scalar dtypes)
+/// to the NPY `descr` string. Returns `None` for anything that isn't a
+/// plain scalar dtype (structured/object arrays), so the caller can fall
+/// back to the numpy-based path.
+fn descr_for_format(format: &str, itemsize: isize) -> Option<&'static str> {
+    // Buffer protocol formats are native-endian by default; numpy's
+    // in-memory arrays are always little-endian on the platforms we ship.
+    Some(match format.trim_start_matches(['@', '=', '<']) {
+        "f" => "<f4",
+        "d" => "<f8",
+        "e" => "<f2",
+        "b" => "<i1",
+        "B" | "c" => "<u1",
+        "?" => "|b1",
+        "h" => "<i2",
+        "H" => "<u2",
+        "i" | "l" if itemsize == 4 => "<i4",
+        "i" | "l" if itemsize == 8 => "<i8",
+        "I" | "L" if itemsize == 4 => "<u4",
+        "I" | "L" if itemsize == 8 => "<u8",
+        "q" => "<i8",
+        "Q" => "<u8",
+        _ => return None,
+    })
+}
+
+/// Builds the ASCII NPY header (magic, version, length, padded dict text)
+/// for an array with the given `descr`/`shape`.
+fn build_npy_header(descr: &str, shape: &[isize]) -> Vec<u8> {
+    let shape_str = if shape.len() == 1 {
+        format!("({},)", shape[0])
+    } else {
+        format!(
+            "({})",
+            shape
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    };
+    let mut dict = format!(
+        "{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape_str}, }}"
+    );
+
+    // Pad with spaces so magic(6) + version(2) + len(2) + dict + '\n' lands
+    // on a 64-byte boundary, then terminate with a newline as the spec
+    // requires.
+    let prefix_len = NPY_MAGIC.len() + NPY_VERSION.len() + 2;
+    let unpadded_len = prefix_len + dict.len() + 1;
+    let padding = (NPY_HEADER_ALIGNMENT - (unpadded_len % NPY_HEADER_ALIGNMENT)) % NPY_HEADER_ALIGNMENT;
+    dict.push_str(&" ".repeat(padding));
+    dict.push('\n');
+
+    let mut header = Vec::with_capacity(prefix_len + dict.len());
+    header.extend_from_slice(NPY_MAGIC);
+    header.extend_from_slice(&NPY_VERSION);
+    header.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+    header.extend_from_slice(dict.as_bytes());
+    header
+}
+
+/// Serializes a numpy array directly to `.npy` bytes via the buffer
+/// protocol, with no Python call and no intermediate `bytes` allocation
+/// beyond the output itself. Returns `None` for arrays the fast path
+/// doesn't cover (non-contiguous, or a dtype `descr_for_format` doesn't
+/// recognize), so the caller can fall back to [`array_to_npy_bytes`].
+unsafe fn array_to_npy_bytes_fast(arr: *mut PyObject) -> Option<Vec<u8>> {
+    let mut view: Py_buffer = std::mem::zeroed();
+    if PyObject_GetBuffer(arr, &mut view, PyBUF_ND | PyBUF_FORMAT) != 0 {
+        PyErr_Clear();
+        return None;
+    }
+
+    // C-contiguous is required; Fortran-order/strided arrays fall back to
+    // the numpy-based path rather than us reimplementing strided copies.
+    if PyBuffer_IsContiguous(&view, b'C' as c_char) == 0 {
+        PyBuffer_Release(&mut view);
+        return None;
+    }
+
+    let format = if view.format.is_null() {
+        "B"
+    } else {
+        std::ffi::CStr::from_ptr(view.format).to_str().unwrap_or("B")
+    };
+    let Some(descr) = descr_for_format(format, view.itemsize) else {
+        PyBuffer_Release(&mut view);
+        return None;
+    };
+
+    let shape: Vec<isize> = if view.shape.is_null() {
+        vec![view.len / view.itemsize]
+    } else {
+        std::slice::from_raw_parts(view.shape, view.ndim as usize).to_vec()
+    };
+
+    let mut bytes = build_npy_header(descr, &shape);
+    bytes.extend_from_slice(std::slice::from_raw_parts(view.buf as *const u8, view.len as usize));
+    PyBuffer_Release(&mut view);
+    Some(bytes)
 }
 
 /// Serialize a numpy array to bytes in .npy format
 unsafe fn array_to_npy_bytes(arr: *mut PyObject) -> Result<Vec<u8>, LogitNpzError> {
+    if let Some(bytes) = array_to_npy_bytes_fast(arr) {
+        return Ok(bytes);
+    }
     let save_func = get_numpy_save().ok_or(LogitNpzError::NumpyNotAvailable)?;
     let bytesio_class = get_bytesio().ok_or(LogitNpzError::NumpyNotAvailable)?;
 
@@ -194,8 +469,154 @@ unsafe fn array_to_npy_bytes(arr: *mut PyObject) -> Result<Vec<u8>, LogitNpzErro
     Ok(bytes)
 }
 
+/// Parsed NPY header: dtype descr string, shape, and the byte offset the
+/// raw array data starts at.
+struct NpyHeader {
+    descr: String,
+    shape: Vec<isize>,
+    fortran_order: bool,
+    data_offset: usize,
+}
+
+/// Tokenizes just enough of the NPY header's Python-dict-literal text
+/// (`{'descr': ..., 'fortran_order': ..., 'shape': (...), }`) to pull out
+/// the three fields we need, without pulling in a full Python parser.
+/// Only version 1.0 headers with a plain (non-structured) `descr` are
+/// accepted.
+fn parse_npy_header(data: &[u8]) -> Option<NpyHeader> {
+    if data.len() < 10 || &data[0..6] != NPY_MAGIC || data[6..8] != NPY_VERSION {
+        return None;
+    }
+    let header_len = u16::from_le_bytes([data[8], data[9]]) as usize;
+    let header_start = 10;
+    let header_end = header_start + header_len;
+    let dict_text = std::str::from_utf8(data.get(header_start..header_end)?).ok()?;
+
+    let descr_text = dict_text.split("'descr':").nth(1)?.trim_start();
+    // A structured dtype's descr is a list, not a quoted string.
+    if !descr_text.starts_with('\'') {
+        return None;
+    }
+    let descr = descr_text.split('\'').nth(1)?.to_string();
+    let fortran_order = dict_text
+        .split("'fortran_order':")
+        .nth(1)?
+        .trim_start()
+        .starts_with("True");
+    let shape_text = dict_text
+        .split("'shape':")
+        .nth(1)?
+        .split('(')
+        .nth(1)?
+        .split(')')
+        .next()?;
+    let shape = shape_text
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<isize>())
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+
+    Some(NpyHeader {
+        descr,
+        shape,
+        fortran_order,
+        data_offset: header_end,
+    })
+}
+
+/// Deserializes `.npy` bytes into a numpy array without going through
+/// `numpy.load`/`BytesIO`: the header is parsed in Rust and the raw data
+/// handed to `numpy.frombuffer` plus a reshape, skipping the full npy
+/// format-dispatch `numpy.load` does internally. Returns `None` (for the
+/// caller to fall back to [`npy_bytes_to_array`]) for anything
+/// `parse_npy_header` can't confidently parse, and for Fortran-ordered
+/// arrays since `frombuffer` always produces C order.
+unsafe fn npy_bytes_to_array_fast(data: &[u8]) -> Option<*mut PyObject> {
+    let header = parse_npy_header(data)?;
+    if header.fortran_order {
+        return None;
+    }
+    let payload = data.get(header.data_offset..)?;
+    let frombuffer = get_numpy_frombuffer()?;
+
+    let bytes_obj = nonnull!(PyBytes_FromStringAndSize(
+        payload.as_ptr() as *const c_char,
+        payload.len() as isize
+    ));
+    let descr_obj = nonnull!(PyUnicode_FromStringAndSize(
+        header.descr.as_ptr() as *const c_char,
+        header.descr.len() as isize
+    ));
+
+    let args = nonnull!(PyTuple_New(1));
+    PyTuple_SET_ITEM(args.as_ptr(), 0, bytes_obj.as_ptr());
+    let kwargs = PyDict_New();
+    PyDict_SetItemString(kwargs, "dtype\0".as_ptr() as *const c_char, descr_obj.as_ptr());
+
+    let flat = PyObject_Call(frombuffer, args.as_ptr(), kwargs);
+    Py_DECREF(args.as_ptr());
+    Py_DECREF(kwargs);
+    Py_DECREF(descr_obj.as_ptr());
+    Py_DECREF(frombuffer);
+    if flat.is_null() {
+        // Fall back to the numpy.load path; that call must not start with
+        // a pending exception from this one still set.
+        PyErr_Clear();
+        return None;
+    }
+
+    let shape_tuple = PyTuple_New(header.shape.len() as isize);
+    for (i, dim) in header.shape.iter().enumerate() {
+        PyTuple_SET_ITEM(shape_tuple, i as isize, PyLong_FromSsize_t(*dim));
+    }
+    let reshape_args = PyTuple_New(1);
+    PyTuple_SET_ITEM(reshape_args, 0, shape_tuple);
+    let reshape = PyObject_GetAttrString(flat, "reshape\0".as_ptr() as *const c_char);
+    Py_DECREF(flat);
+    if reshape.is_null() {
+        Py_DECREF(reshape_args);
+        PyErr_Clear();
+        return None;
+    }
+    let result = PyObject_Call(reshape, reshape_args, std::ptr::null_mut());
+    Py_DECREF(reshape_args);
+    Py_DECREF(reshape);
+    if result.is_null() {
+        PyErr_Clear();
+        return None;
+    }
+
+    // `frombuffer` (and therefore `result`, a reshaped view over it) is
+    // always read-only, since it aliases the `bytes` object's immutable
+    // storage; `numpy.load` (the fallback this function exists to avoid)
+    // returns a writable array. Match that contract with one copy, rather
+    // than silently handing back a read-only array a caller expects to be
+    // able to mutate in place.
+    let copy_method = PyObject_GetAttrString(result, "copy\0".as_ptr() as *const c_char);
+    if copy_method.is_null() {
+        Py_DECREF(result);
+        PyErr_Clear();
+        return None;
+    }
+    let empty_args = PyTuple_New(0);
+    let writable = PyObject_Call(copy_method, empty_args, std::ptr::null_mut());
+    Py_DECREF(empty_args);
+    Py_DECREF(copy_method);
+    Py_DECREF(result);
+    if writable.is_null() {
+        PyErr_Clear();
+        return None;
+    }
+    Some(writable)
+}
+
 /// Deserialize bytes in .npy format to a numpy array
 unsafe fn npy_bytes_to_array(data: &[u8]) -> Result<*mut PyObject, LogitNpzError> {
+    if let Some(arr) = npy_bytes_to_array_fast(data) {
+        return Ok(arr);
+    }
     let load_func = get_numpy_load().ok_or(LogitNpzError::NumpyNotAvailable)?;
     let bytesio_class = get_bytesio().ok_or(LogitNpzError::NumpyNotAvailable)?;
 
@@ -238,11 +659,452 @@ unsafe fn npy_bytes_to_array(data: &[u8]) -> Result<*mut PyObject, LogitNpzError
     Ok(arr)
 }
 
+/// Archive-wide compression defaults plus an optional per-array override
+/// map, resolved once up front and consulted per member while writing.
+pub struct CompressionSpec {
+    pub default_codec: CompressionCodec,
+    pub level: i64,
+    pub per_array: Vec<(String, CompressionCodec)>,
+}
+
+impl CompressionSpec {
+    fn options_for(&self, key: &str) -> Result<SimpleFileOptions, LogitNpzError> {
+        codec_for_key(self.default_codec, &self.per_array, key).options(self.level)
+    }
+}
+
+/// One array's entry in the manifest: enough to order the archive
+/// deterministically and to catch a decoded array silently disagreeing
+/// with what was written.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct ManifestEntry {
+    name: String,
+    dtype: String,
+    shape: Vec<i64>,
+    compression: String,
+    /// `false` for an entry whose `dtype`/`shape` are placeholders because
+    /// [`parse_npy_header`] couldn't read the member's actual npy header
+    /// (a fallback `numpy.save` output it doesn't understand, a structured
+    /// dtype, or any header format beyond version 1.0). Readers
+    /// must still list and load the member -- they just skip the
+    /// dtype/shape cross-check against the manifest for it. Older
+    /// manifests predate this field and are treated as fully validated.
+    #[serde(default = "default_true")]
+    validated: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Builds this array's manifest entry from its already-serialized npy
+/// bytes, always -- even when [`parse_npy_header`] can't make sense of the
+/// header -- so every written member is guaranteed a manifest entry and
+/// none can silently fall out of the archive's member list on load.
+fn manifest_entry_for(key_str: &str, npy_bytes: &[u8], codec: CompressionCodec) -> ManifestEntry {
+    match parse_npy_header(npy_bytes) {
+        Some(header) => ManifestEntry {
+            name: key_str.to_string(),
+            dtype: header.descr,
+            shape: header.shape.iter().map(|d| *d as i64).collect(),
+            compression: codec.name().to_string(),
+            validated: true,
+        },
+        None => ManifestEntry {
+            name: key_str.to_string(),
+            dtype: String::new(),
+            shape: Vec::new(),
+            compression: codec.name().to_string(),
+            validated: false,
+        },
+    }
+}
+
+/// The full `manifest.cbor` member: per-array metadata in original
+/// insertion order, plus the caller's free-form `attrs`.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct Manifest {
+    entries: Vec<ManifestEntry>,
+    #[serde(default)]
+    attrs: Option<CborValue>,
+}
+
+/// Converts a Python value to CBOR for the `attrs` dict: `None`, `bool`,
+/// `int`, `float`, `str`, and `list`/`dict` of the same, recursively.
+unsafe fn pyobject_to_cbor(obj: *mut PyObject) -> Result<CborValue, LogitNpzError> {
+    if obj == crate::typeref::NONE {
+        Ok(CborValue::Null)
+    } else if PyBool_Check(obj) != 0 {
+        Ok(CborValue::Bool(obj == crate::typeref::TRUE))
+    } else if PyLong_Check(obj) != 0 {
+        let val = PyLong_AsLongLong(obj);
+        if val == -1 && !PyErr_Occurred().is_null() {
+            PyErr_Clear();
+            return Err(LogitNpzError::InvalidFormat(
+                "attrs int is out of range for a 64-bit integer".to_string(),
+            ));
+        }
+        Ok(CborValue::Integer(val.into()))
+    } else if PyFloat_Check(obj) != 0 {
+        Ok(CborValue::Float(PyFloat_AS_DOUBLE(obj)))
+    } else if PyUnicode_Check(obj) != 0 {
+        Ok(CborValue::Text(pystr(obj, "attrs key/value")?.to_string()))
+    } else if PyList_Check(obj) != 0 || PyTuple_Check(obj) != 0 {
+        let len = PySequence_Size(obj);
+        let mut items = Vec::with_capacity(len.max(0) as usize);
+        for i in 0..len {
+            let item = PySequence_GetItem(obj, i);
+            let converted = pyobject_to_cbor(item)?;
+            Py_DECREF(item);
+            items.push(converted);
+        }
+        Ok(CborValue::Array(items))
+    } else if PyDict_Check(obj) != 0 {
+        let mut pairs = Vec::new();
+        let mut pos: Py_ssize_t = 0;
+        let mut key: *mut PyObject = std::ptr::null_mut();
+        let mut value: *mut PyObject = std::ptr::null_mut();
+        while PyDict_Next(obj, &mut pos, &mut key, &mut value) != 0 {
+            pairs.push((pyobject_to_cbor(key)?, pyobject_to_cbor(value)?));
+        }
+        Ok(CborValue::Map(pairs))
+    } else {
+        Err(LogitNpzError::InvalidFormat(
+            "attrs may only contain None, bool, int, float, str, list, and dict".to_string(),
+        ))
+    }
+}
+
+/// Converts a CBOR value read back from the manifest to a Python object,
+/// the inverse of [`pyobject_to_cbor`].
+unsafe fn cbor_to_pyobject(value: &CborValue) -> Result<*mut PyObject, LogitNpzError> {
+    match value {
+        CborValue::Null => {
+            Py_INCREF(crate::typeref::NONE);
+            Ok(crate::typeref::NONE)
+        }
+        CborValue::Bool(b) => {
+            let obj = if *b { crate::typeref::TRUE } else { crate::typeref::FALSE };
+            Py_INCREF(obj);
+            Ok(obj)
+        }
+        CborValue::Integer(i) => {
+            let val = i64::try_from(*i).map_err(|_| {
+                LogitNpzError::InvalidFormat(format!(
+                    "manifest attrs integer `{i:?}` does not fit in a 64-bit integer"
+                ))
+            })?;
+            Ok(PyLong_FromLongLong(val))
+        }
+        CborValue::Float(f) => Ok(PyFloat_FromDouble(*f)),
+        CborValue::Text(s) => Ok(PyUnicode_FromStringAndSize(
+            s.as_ptr() as *const c_char,
+            s.len() as isize,
+        )),
+        CborValue::Array(items) => {
+            let list = PyList_New(items.len() as Py_ssize_t);
+            for (i, item) in items.iter().enumerate() {
+                PyList_SET_ITEM(list, i as Py_ssize_t, cbor_to_pyobject(item)?);
+            }
+            Ok(list)
+        }
+        CborValue::Map(pairs) => {
+            let dict = PyDict_New();
+            for (k, v) in pairs {
+                let key_obj = cbor_to_pyobject(k)?;
+                let value_obj = cbor_to_pyobject(v)?;
+                PyDict_SetItem(dict, key_obj, value_obj);
+                Py_DECREF(key_obj);
+                Py_DECREF(value_obj);
+            }
+            Ok(dict)
+        }
+        _ => Err(LogitNpzError::InvalidFormat(
+            "unsupported CBOR value in manifest attrs".to_string(),
+        )),
+    }
+}
+
+/// Writes `manifest.cbor` describing `entries` (and `attrs`, if given) as
+/// the last member of the archive.
+fn write_manifest<W: Write + std::io::Seek>(
+    zip: &mut ZipWriter<W>,
+    entries: Vec<ManifestEntry>,
+    attrs: Option<CborValue>,
+    options: SimpleFileOptions,
+) -> Result<(), LogitNpzError> {
+    let manifest = Manifest { entries, attrs };
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&manifest, &mut bytes)
+        .map_err(|e| LogitNpzError::InvalidFormat(format!("failed to encode manifest: {e}")))?;
+    zip.start_file(MANIFEST_FILENAME, options)?;
+    zip.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Reads back `manifest.cbor` from an already-open archive, if present.
+fn read_manifest<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<Option<Manifest>, LogitNpzError> {
+    let Ok(mut entry) = archive.by_name(MANIFEST_FILENAME) else {
+        return Ok(None);
+    };
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes)?;
+    let manifest: Manifest = ciborium::from_reader(bytes.as_slice())
+        .map_err(|e| LogitNpzError::InvalidFormat(format!("failed to decode manifest: {e}")))?;
+    Ok(Some(manifest))
+}
+
+/// Borrows a Python `str` object as a `&str`, for the common "path/key
+/// argument must be a string" validation repeated across the Python-facing
+/// entry points.
+unsafe fn pystr<'a>(obj: *mut PyObject, arg_name: &str) -> Result<&'a str, LogitNpzError> {
+    if PyUnicode_Check(obj) == 0 {
+        return Err(LogitNpzError::InvalidFormat(format!(
+            "{arg_name} must be a string"
+        )));
+    }
+    let mut size: Py_ssize_t = 0;
+    let ptr = PyUnicode_AsUTF8AndSize(obj, &mut size);
+    if ptr.is_null() {
+        return Err(LogitNpzError::PythonError);
+    }
+    Ok(std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+        ptr as *const u8,
+        size as usize,
+    )))
+}
+
+/// Lists array names in a logitnpz archive by reading only the zip central
+/// directory, without decompressing any member.
+pub unsafe fn logitnpz_keys_list(path: *mut PyObject) -> Result<Vec<String>, LogitNpzError> {
+    let path_str = pystr(path, "path")?;
+    let file = File::open(path_str)?;
+    let archive = ZipArchive::new(file)?;
+    Ok(archive
+        .file_names()
+        .filter(|name| name.ends_with(".npy"))
+        .map(|name| name[..name.len() - 4].to_string())
+        .collect())
+}
+
+/// Loads a single array from a logitnpz archive, seeking straight to its
+/// member via the central directory and decompressing only that entry --
+/// the rest of the archive is left untouched.
+pub unsafe fn load_logitnpz_one(
+    path: *mut PyObject,
+    name: &str,
+) -> Result<*mut PyObject, LogitNpzError> {
+    let path_str = pystr(path, "path")?;
+    let file = File::open(path_str)?;
+    let mut archive = ZipArchive::new(file)?;
+    let filename = format!("{name}.npy");
+    let mut entry = archive.by_name(&filename).map_err(|_| {
+        LogitNpzError::InvalidFormat(format!("no array named `{name}` in archive"))
+    })?;
+    let mut data = Vec::new();
+    entry.read_to_end(&mut data)?;
+    npy_bytes_to_array(&data)
+}
+
+/// Everything a mmap-backed array's capsule needs to keep alive for as long
+/// as the array (or any view derived from it) exists: the `Arc<Mmap>`
+/// itself, plus the `Py_buffer` shape/strides storage `PyMemoryView_FromBuffer`
+/// only borrows a pointer into -- boxing them together means one capsule
+/// destructor frees both instead of the shape/strides arrays being leaked
+/// forever on every call.
+struct MmapArrayOwner {
+    mmap: Arc<Mmap>,
+    shape: [Py_ssize_t; 1],
+    strides: [Py_ssize_t; 1],
+}
+
+/// `PyCapsule` destructor that drops the [`MmapArrayOwner`] (and with it the
+/// `Arc<Mmap>` and shape/strides storage) pinned to a mmap-backed array's
+/// lifetime once that array (and whatever views over it) are gone.
+unsafe extern "C" fn drop_mmap_capsule(capsule: *mut PyObject) {
+    let ptr = PyCapsule_GetPointer(capsule, std::ptr::null());
+    if !ptr.is_null() {
+        drop(Box::from_raw(ptr as *mut MmapArrayOwner));
+    }
+}
+
+/// Builds a numpy array that views `mmap[start..end]` (a `stored`, i.e.
+/// uncompressed, archive member) directly -- no copy into the heap -- and
+/// pins `mmap` alive for the array's lifetime via a capsule set as its
+/// `base` attribute, the same convention numpy itself uses for buffer
+/// owners.
+unsafe fn mmap_stored_array(
+    mmap: &Arc<Mmap>,
+    start: usize,
+    end: usize,
+) -> Result<*mut PyObject, LogitNpzError> {
+    if start > end || end > mmap.len() {
+        return Err(LogitNpzError::InvalidFormat(
+            "mmap'd member's recorded range is out of bounds for the archive file".to_string(),
+        ));
+    }
+    let data = &mmap[start..end];
+    let header = parse_npy_header(data)
+        .ok_or_else(|| LogitNpzError::InvalidFormat("invalid npy header in mmap'd member".to_string()))?;
+    if header.data_offset > data.len() {
+        return Err(LogitNpzError::InvalidFormat(
+            "mmap'd member's npy header claims a data offset past the end of its range".to_string(),
+        ));
+    }
+    let payload_ptr = data.as_ptr().add(header.data_offset);
+    let payload_len = data.len() - header.data_offset;
+
+    // `PyMemoryView_FromMemory` has no owning object at all, so there is
+    // nothing to pin the backing `Arc<Mmap>` alive once this function
+    // returns. Build the `Py_buffer` by hand instead, with `obj` set to a
+    // capsule holding the `Arc<Mmap>`: `numpy.frombuffer` sets the
+    // resulting array's `base` to whatever buffer-protocol object it was
+    // given, so the capsule -- and the mapping it keeps alive -- survives
+    // for as long as the array (or any view derived from it) does.
+    let owner_ptr = Box::into_raw(Box::new(MmapArrayOwner {
+        mmap: Arc::clone(mmap),
+        shape: [payload_len as Py_ssize_t],
+        strides: [1_i64],
+    }));
+    let capsule = PyCapsule_New(
+        owner_ptr as *mut core::ffi::c_void,
+        std::ptr::null(),
+        Some(drop_mmap_capsule),
+    );
+    if capsule.is_null() {
+        drop(Box::from_raw(owner_ptr));
+        return Err(LogitNpzError::PythonError);
+    }
+
+    static FORMAT: &[u8] = b"B\0";
+    // `PyMemoryView_FromBuffer` takes ownership of the reference in
+    // `buffer.obj` without incref'ing it itself, so give it its own.
+    Py_INCREF(capsule);
+    let mut buffer = Py_buffer {
+        buf: payload_ptr as *mut core::ffi::c_void,
+        obj: capsule,
+        len: payload_len as Py_ssize_t,
+        itemsize: 1,
+        readonly: 1,
+        ndim: 1,
+        format: FORMAT.as_ptr() as *mut c_char,
+        shape: (*owner_ptr).shape.as_mut_ptr(),
+        strides: (*owner_ptr).strides.as_mut_ptr(),
+        suboffsets: std::ptr::null_mut(),
+        internal: std::ptr::null_mut(),
+    };
+    let view = PyMemoryView_FromBuffer(&mut buffer);
+    if view.is_null() {
+        // `buffer.obj`'s reference was not consumed; drop it ourselves.
+        Py_DECREF(capsule);
+        Py_DECREF(capsule);
+        return Err(LogitNpzError::PythonError);
+    }
+
+    let descr_obj = PyUnicode_FromStringAndSize(
+        header.descr.as_ptr() as *const c_char,
+        header.descr.len() as isize,
+    );
+    let args = PyTuple_New(1);
+    PyTuple_SET_ITEM(args, 0, view);
+    let kwargs = PyDict_New();
+    PyDict_SetItemString(kwargs, "dtype\0".as_ptr() as *const c_char, descr_obj);
+    let frombuffer = get_numpy_frombuffer().ok_or(LogitNpzError::NumpyNotAvailable)?;
+    let flat = PyObject_Call(frombuffer, args, kwargs);
+    Py_DECREF(args);
+    Py_DECREF(kwargs);
+    Py_DECREF(descr_obj);
+    Py_DECREF(frombuffer);
+    Py_DECREF(capsule);
+    if flat.is_null() {
+        return Err(LogitNpzError::PythonError);
+    }
+
+    let shape_tuple = PyTuple_New(header.shape.len() as isize);
+    for (i, dim) in header.shape.iter().enumerate() {
+        PyTuple_SET_ITEM(shape_tuple, i as isize, PyLong_FromSsize_t(*dim));
+    }
+    let reshape_args = PyTuple_New(1);
+    PyTuple_SET_ITEM(reshape_args, 0, shape_tuple);
+    let reshape = PyObject_GetAttrString(flat, "reshape\0".as_ptr() as *const c_char);
+    Py_DECREF(flat);
+    if reshape.is_null() {
+        Py_DECREF(reshape_args);
+        return Err(LogitNpzError::PythonError);
+    }
+    let arr = PyObject_Call(reshape, reshape_args, std::ptr::null_mut());
+    Py_DECREF(reshape_args);
+    Py_DECREF(reshape);
+    if arr.is_null() {
+        return Err(LogitNpzError::PythonError);
+    }
+    Ok(arr)
+}
+
+/// Loads every `.npy` member of a logitnpz file into a dict, with `stored`
+/// (uncompressed) members returned as zero-copy arrays backed by a memory
+/// map of the file instead of being copied into the heap; compressed
+/// members still decompress into an owned buffer. Unlike
+/// [`load_logitnpz`], members are read in zip directory order and the
+/// manifest is neither read nor checked against.
+pub unsafe fn load_logitnpz_mmap(path: *mut PyObject) -> Result<*mut PyObject, LogitNpzError> {
+    let path_str = pystr(path, "path")?;
+    let file = File::open(path_str)?;
+    let mmap = Arc::new(Mmap::map(&file)?);
+    let mut archive = ZipArchive::new(Cursor::new(&mmap[..]))?;
+
+    let result_dict = PyDict_New();
+    if result_dict.is_null() {
+        return Err(LogitNpzError::PythonError);
+    }
+
+    for i in 0..archive.len() {
+        let (name, is_stored, start, end) = {
+            let entry = archive.by_index(i)?;
+            (
+                entry.name().to_string(),
+                entry.compression() == CompressionMethod::Stored,
+                entry.data_start() as usize,
+                entry.data_start() as usize + entry.size() as usize,
+            )
+        };
+        if !name.ends_with(".npy") {
+            continue;
+        }
+        let key_name = &name[..name.len() - 4];
+
+        let arr = if is_stored {
+            mmap_stored_array(&mmap, start, end)?
+        } else {
+            let mut entry = archive.by_index(i)?;
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            npy_bytes_to_array(&data)?
+        };
+
+        let key_obj =
+            PyUnicode_FromStringAndSize(key_name.as_ptr() as *const c_char, key_name.len() as isize);
+        if PyDict_SetItem(result_dict, key_obj, arr) < 0 {
+            Py_DECREF(key_obj);
+            Py_DECREF(arr);
+            Py_DECREF(result_dict);
+            return Err(LogitNpzError::PythonError);
+        }
+        Py_DECREF(key_obj);
+        Py_DECREF(arr);
+    }
+
+    Ok(result_dict)
+}
+
 /// Save a dict of numpy arrays to a logitnpz file
 pub unsafe fn save_logitnpz(
     path: *mut PyObject,
     arrays: *mut PyObject,
-    compression_level: i64,
+    spec: &CompressionSpec,
+    attrs: *mut PyObject,
 ) -> Result<(), LogitNpzError> {
     // Get path as string
     let path_str = if PyUnicode_Check(path) != 0 {
@@ -267,10 +1129,7 @@ pub unsafe fn save_logitnpz(
 
     let file = File::create(path_str)?;
     let mut zip = ZipWriter::new(file);
-
-    let options = SimpleFileOptions::default()
-        .compression_method(CompressionMethod::Zstd)
-        .compression_level(Some(compression_level));
+    let mut manifest_entries = Vec::new();
 
     // Iterate over dict items
     let mut pos: Py_ssize_t = 0;
@@ -295,16 +1154,144 @@ pub unsafe fn save_logitnpz(
             ));
         };
 
-        // Convert array to npy bytes
-        let npy_bytes = array_to_npy_bytes(value)?;
+        // Convert array to npy bytes
+        let npy_bytes = array_to_npy_bytes(value)?;
+        let codec = codec_for_key(spec.default_codec, &spec.per_array, key_str);
+        manifest_entries.push(manifest_entry_for(key_str, &npy_bytes, codec));
+
+        // Write to zip with .npy extension
+        let filename = format!("{}.npy", key_str);
+        zip.start_file(&filename, spec.options_for(key_str)?)?;
+        zip.write_all(&npy_bytes)?;
+    }
+
+    let attrs_cbor = if attrs.is_null() {
+        None
+    } else {
+        Some(pyobject_to_cbor(attrs)?)
+    };
+    write_manifest(
+        &mut zip,
+        manifest_entries,
+        attrs_cbor,
+        spec.default_codec.options(spec.level)?,
+    )?;
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// Adds `arrays` to an existing logitnpz file without re-encoding any
+/// member already in it: every untouched existing member is streamed
+/// into the rewritten archive with [`ZipWriter::raw_copy_file`] (compressed
+/// bytes copied verbatim, no decompress/recompress round trip), and only
+/// the new or `overwrite`-replaced members are freshly encoded. The `zip`
+/// format's central directory sits at the end of the file, so there is no
+/// way to add a member in place -- the archive is rewritten to a sibling
+/// temp file and renamed over the original once that rewrite succeeds.
+pub unsafe fn append_logitnpz(
+    path: *mut PyObject,
+    arrays: *mut PyObject,
+    spec: &CompressionSpec,
+    overwrite: bool,
+) -> Result<(), LogitNpzError> {
+    let path_str = pystr(path, "path")?;
+    if PyDict_Check(arrays) == 0 {
+        return Err(LogitNpzError::InvalidFormat(
+            "arrays must be a dict".to_string(),
+        ));
+    }
+
+    let mut new_keys = Vec::new();
+    let mut pos: Py_ssize_t = 0;
+    let mut key: *mut PyObject = std::ptr::null_mut();
+    let mut value: *mut PyObject = std::ptr::null_mut();
+    while PyDict_Next(arrays, &mut pos, &mut key, &mut value) != 0 {
+        new_keys.push((pystr(key, "array name")?.to_string(), value));
+    }
+
+    let reader_file = File::open(path_str)?;
+    let mut reader = ZipArchive::new(reader_file)?;
+    let manifest = read_manifest(&mut reader)?;
+
+    if !overwrite {
+        for (key_str, _) in &new_keys {
+            if reader.by_name(&format!("{key_str}.npy")).is_ok() {
+                return Err(LogitNpzError::InvalidFormat(format!(
+                    "array `{key_str}` already exists in archive (pass overwrite=True to replace it)"
+                )));
+            }
+        }
+    }
+
+    let tmp_path = format!("{path_str}.tmp");
+    let writer_file = File::create(&tmp_path)?;
+    let mut zip = ZipWriter::new(writer_file);
+
+    let (mut manifest_entries, attrs_cbor) = manifest
+        .map(|m| (m.entries, m.attrs))
+        .unwrap_or((Vec::new(), None));
+
+    // Seed the rewritten manifest with an entry for every `.npy` member
+    // the archive actually has, not just the ones the old manifest
+    // happened to list: a foreign/no-manifest archive, or one whose
+    // manifest doesn't list every member, would
+    // otherwise have those members copied into the new archive verbatim
+    // but never listed -- and since `write_manifest` runs unconditionally
+    // below, every subsequent `load_logitnpz*` call would only ever see
+    // the keys being appended now, silently hiding the rest even though
+    // their bytes are still physically present.
+    for name in reader.file_names() {
+        if !name.ends_with(".npy") {
+            continue;
+        }
+        let key_str = &name[..name.len() - 4];
+        if !manifest_entries.iter().any(|e| e.name == key_str) {
+            manifest_entries.push(ManifestEntry {
+                name: key_str.to_string(),
+                dtype: String::new(),
+                shape: Vec::new(),
+                compression: String::from("unknown"),
+                validated: false,
+            });
+        }
+    }
 
-        // Write to zip with .npy extension
-        let filename = format!("{}.npy", key_str);
-        zip.start_file(&filename, options)?;
+    for i in 0..reader.len() {
+        let entry = reader.by_index_raw(i)?;
+        let name = entry.name().to_string();
+        if name == MANIFEST_FILENAME {
+            continue;
+        }
+        let key_str = name.strip_suffix(".npy").unwrap_or(&name);
+        if new_keys.iter().any(|(k, _)| k == key_str) {
+            continue;
+        }
+        zip.raw_copy_file(entry)?;
+    }
+    drop(reader);
+
+    for (key_str, value) in &new_keys {
+        manifest_entries.retain(|e| &e.name != key_str);
+
+        let npy_bytes = array_to_npy_bytes(*value)?;
+        let codec = codec_for_key(spec.default_codec, &spec.per_array, key_str);
+        manifest_entries.push(manifest_entry_for(key_str, &npy_bytes, codec));
+
+        let filename = format!("{key_str}.npy");
+        zip.start_file(&filename, spec.options_for(key_str)?)?;
         zip.write_all(&npy_bytes)?;
     }
 
+    write_manifest(
+        &mut zip,
+        manifest_entries,
+        attrs_cbor,
+        spec.default_codec.options(spec.level)?,
+    )?;
     zip.finish()?;
+
+    std::fs::rename(&tmp_path, path_str)?;
     Ok(())
 }
 
@@ -326,40 +1313,69 @@ pub unsafe fn load_logitnpz(path: *mut PyObject) -> Result<*mut PyObject, LogitN
 
     let file = File::open(path_str)?;
     let mut archive = ZipArchive::new(file)?;
+    let manifest = read_manifest(&mut archive)?;
+
+    load_archive_entries(&mut archive, manifest)
+}
 
+/// Shared body of [`load_logitnpz`]/[`load_logitnpz_bytes`]: reads every
+/// `.npy` member into the result dict, in manifest order (validating each
+/// array's dtype/shape against its manifest entry) when a manifest was
+/// found, else in whatever order the zip directory lists them.
+unsafe fn load_archive_entries<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    manifest: Option<Manifest>,
+) -> Result<*mut PyObject, LogitNpzError> {
     let result_dict = PyDict_New();
     if result_dict.is_null() {
         return Err(LogitNpzError::PythonError);
     }
 
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i)?;
-        let name = file.name().to_string();
-
-        // Only process .npy files
-        if !name.ends_with(".npy") {
-            continue;
-        }
+    let names: Vec<String> = match &manifest {
+        Some(m) => m.entries.iter().map(|e| e.name.clone()).collect(),
+        None => archive
+            .file_names()
+            .filter(|n| n.ends_with(".npy"))
+            .map(|n| n[..n.len() - 4].to_string())
+            .collect(),
+    };
 
-        // Read file contents
+    for key_name in &names {
+        let filename = format!("{key_name}.npy");
+        let mut file = archive.by_name(&filename)?;
         let mut data = Vec::new();
         file.read_to_end(&mut data)?;
+        drop(file);
+
+        if let Some(m) = &manifest {
+            if let Some(entry) = m.entries.iter().find(|e| &e.name == key_name) {
+                // `validated: false` entries are placeholders recorded
+                // because the manifest writer couldn't parse this
+                // member's own header either; there's nothing to
+                // cross-check it against.
+                if entry.validated {
+                    let header = parse_npy_header(&data).ok_or_else(|| {
+                        LogitNpzError::InvalidFormat(format!("`{key_name}` has an invalid npy header"))
+                    })?;
+                    let shape: Vec<i64> = header.shape.iter().map(|d| *d as i64).collect();
+                    if header.descr != entry.dtype || shape != entry.shape {
+                        return Err(LogitNpzError::InvalidFormat(format!(
+                            "`{key_name}` does not match its manifest entry (expected dtype {} shape {:?}, got dtype {} shape {:?})",
+                            entry.dtype, entry.shape, header.descr, shape
+                        )));
+                    }
+                }
+            }
+        }
 
-        // Convert to numpy array
         let arr = npy_bytes_to_array(&data)?;
-
-        // Get key name (remove .npy extension)
-        let key_name = &name[..name.len() - 4];
-        let key_obj =
-            PyUnicode_FromStringAndSize(key_name.as_ptr() as *const c_char, key_name.len() as isize);
-
+        let key_obj = PyUnicode_FromStringAndSize(key_name.as_ptr() as *const c_char, key_name.len() as isize);
         if PyDict_SetItem(result_dict, key_obj, arr) < 0 {
             Py_DECREF(key_obj);
             Py_DECREF(arr);
             Py_DECREF(result_dict);
             return Err(LogitNpzError::PythonError);
         }
-
         Py_DECREF(key_obj);
         Py_DECREF(arr);
     }
@@ -367,10 +1383,180 @@ pub unsafe fn load_logitnpz(path: *mut PyObject) -> Result<*mut PyObject, LogitN
     Ok(result_dict)
 }
 
+/// Returns the `attrs` dict recorded in a logitnpz archive's manifest, or
+/// `None` if the archive has no manifest or no attrs were set.
+pub unsafe fn load_logitnpz_attrs(path: *mut PyObject) -> Result<*mut PyObject, LogitNpzError> {
+    let path_str = pystr(path, "path")?;
+    let file = File::open(path_str)?;
+    let mut archive = ZipArchive::new(file)?;
+    match read_manifest(&mut archive)?.and_then(|m| m.attrs) {
+        Some(attrs) => cbor_to_pyobject(&attrs),
+        None => {
+            Py_INCREF(crate::typeref::NONE);
+            Ok(crate::typeref::NONE)
+        }
+    }
+}
+
+/// State owned by a [`LogitNpzIterObject`], boxed separately from the
+/// Python object header so the header stays a fixed, `repr(C)`-stable size.
+struct LogitNpzIterState {
+    archive: ZipArchive<File>,
+    names: Vec<String>,
+    manifest: Option<Manifest>,
+    index: usize,
+}
+
+/// A Python-visible iterator yielding `(name, array)` pairs one member at a
+/// time: only the member being read is ever decompressed into memory, so a
+/// caller can process an archive far larger than would fit as a fully
+/// materialized [`load_logitnpz`] dict.
+#[repr(C)]
+struct LogitNpzIterObject {
+    ob_base: PyObject,
+    state: *mut LogitNpzIterState,
+}
+
+unsafe extern "C" fn logitnpz_iter_dealloc(obj: *mut PyObject) {
+    let it = obj as *mut LogitNpzIterObject;
+    if !(*it).state.is_null() {
+        drop(Box::from_raw((*it).state));
+    }
+    std::alloc::dealloc(obj as *mut u8, std::alloc::Layout::new::<LogitNpzIterObject>());
+}
+
+unsafe extern "C" fn logitnpz_iter_self(obj: *mut PyObject) -> *mut PyObject {
+    Py_INCREF(obj);
+    obj
+}
+
+unsafe extern "C" fn logitnpz_iter_next(obj: *mut PyObject) -> *mut PyObject {
+    let state = &mut *((*(obj as *mut LogitNpzIterObject)).state);
+    if state.index >= state.names.len() {
+        return std::ptr::null_mut();
+    }
+    let key_name = state.names[state.index].clone();
+    state.index += 1;
+
+    let filename = format!("{key_name}.npy");
+    let mut entry = match state.archive.by_name(&filename) {
+        Ok(entry) => entry,
+        Err(e) => return LogitNpzError::from(e).to_py_error(),
+    };
+    let mut data = Vec::new();
+    if let Err(e) = entry.read_to_end(&mut data) {
+        return LogitNpzError::from(e).to_py_error();
+    }
+    drop(entry);
+
+    if let Some(manifest) = &state.manifest {
+        if let Some(mentry) = manifest.entries.iter().find(|e| e.name == key_name) {
+            if mentry.validated {
+                match parse_npy_header(&data) {
+                    Some(header) => {
+                        let shape: Vec<i64> = header.shape.iter().map(|d| *d as i64).collect();
+                        if header.descr != mentry.dtype || shape != mentry.shape {
+                            return LogitNpzError::InvalidFormat(format!(
+                                "`{key_name}` does not match its manifest entry (expected dtype {} shape {:?}, got dtype {} shape {:?})",
+                                mentry.dtype, mentry.shape, header.descr, shape
+                            ))
+                            .to_py_error();
+                        }
+                    }
+                    None => {
+                        return LogitNpzError::InvalidFormat(format!(
+                            "`{key_name}` has an invalid npy header"
+                        ))
+                        .to_py_error()
+                    }
+                }
+            }
+        }
+    }
+
+    let arr = match npy_bytes_to_array(&data) {
+        Ok(arr) => arr,
+        Err(e) => return e.to_py_error(),
+    };
+    let key_obj =
+        PyUnicode_FromStringAndSize(key_name.as_ptr() as *const c_char, key_name.len() as isize);
+    if key_obj.is_null() {
+        Py_DECREF(arr);
+        return std::ptr::null_mut();
+    }
+    let tuple = PyTuple_New(2);
+    PyTuple_SET_ITEM(tuple, 0, key_obj);
+    PyTuple_SET_ITEM(tuple, 1, arr);
+    tuple
+}
+
+static LOGITNPZ_ITER_TYPE: std::sync::OnceLock<usize> = std::sync::OnceLock::new();
+
+/// Builds (once per interpreter) the static `PyTypeObject` backing
+/// [`LogitNpzIterObject`], the same hand-rolled way the rest of this module
+/// resolves optional globals lazily via `get_or_init`.
+unsafe fn logitnpz_iter_type() -> *mut PyTypeObject {
+    let addr = LOGITNPZ_ITER_TYPE.get_or_init(|| {
+        let ty = Box::leak(Box::new(std::mem::zeroed::<PyTypeObject>()));
+        PyObject_Init(
+            std::ptr::addr_of_mut!(ty.ob_base.ob_base) as *mut PyObject,
+            std::ptr::addr_of_mut!(PyType_Type),
+        );
+        ty.tp_name = "orjson.LogitNpzIterator\0".as_ptr() as *const c_char;
+        ty.tp_basicsize = std::mem::size_of::<LogitNpzIterObject>() as Py_ssize_t;
+        ty.tp_itemsize = 0;
+        ty.tp_flags = Py_TPFLAGS_DEFAULT;
+        ty.tp_dealloc = Some(logitnpz_iter_dealloc);
+        ty.tp_iter = Some(logitnpz_iter_self);
+        ty.tp_iternext = Some(logitnpz_iter_next);
+        if PyType_Ready(ty as *mut PyTypeObject) < 0 {
+            PyErr_Clear();
+        }
+        ty as *mut PyTypeObject as usize
+    });
+    *addr as *mut PyTypeObject
+}
+
+/// Opens `path` and returns a [`LogitNpzIterObject`] that yields `(name,
+/// array)` pairs lazily, in manifest order when a manifest is present.
+pub unsafe fn iter_logitnpz(path: *mut PyObject) -> Result<*mut PyObject, LogitNpzError> {
+    let path_str = pystr(path, "path")?;
+    let file = File::open(path_str)?;
+    let mut archive = ZipArchive::new(file)?;
+    let manifest = read_manifest(&mut archive)?;
+    let names: Vec<String> = match &manifest {
+        Some(m) => m.entries.iter().map(|e| e.name.clone()).collect(),
+        None => archive
+            .file_names()
+            .filter(|n| n.ends_with(".npy"))
+            .map(|n| n[..n.len() - 4].to_string())
+            .collect(),
+    };
+
+    let state = Box::into_raw(Box::new(LogitNpzIterState {
+        archive,
+        names,
+        manifest,
+        index: 0,
+    }));
+
+    let tp = logitnpz_iter_type();
+    let layout = std::alloc::Layout::new::<LogitNpzIterObject>();
+    let obj = std::alloc::alloc(layout) as *mut LogitNpzIterObject;
+    if obj.is_null() {
+        drop(Box::from_raw(state));
+        return Err(LogitNpzError::PythonError);
+    }
+    PyObject_Init(obj as *mut PyObject, tp);
+    (*obj).state = state;
+    Ok(obj as *mut PyObject)
+}
+
 /// Save a dict of numpy arrays to bytes in logitnpz format
 pub unsafe fn save_logitnpz_bytes(
     arrays: *mut PyObject,
-    compression_level: i64,
+    spec: &CompressionSpec,
+    attrs: *mut PyObject,
 ) -> Result<*mut PyObject, LogitNpzError> {
     // Verify arrays is a dict
     if PyDict_Check(arrays) == 0 {
@@ -382,10 +1568,7 @@ pub unsafe fn save_logitnpz_bytes(
     let mut buffer = Cursor::new(Vec::new());
     {
         let mut zip = ZipWriter::new(&mut buffer);
-
-        let options = SimpleFileOptions::default()
-            .compression_method(CompressionMethod::Zstd)
-            .compression_level(Some(compression_level));
+        let mut manifest_entries = Vec::new();
 
         // Iterate over dict items
         let mut pos: Py_ssize_t = 0;
@@ -412,13 +1595,27 @@ pub unsafe fn save_logitnpz_bytes(
 
             // Convert array to npy bytes
             let npy_bytes = array_to_npy_bytes(value)?;
+            let codec = codec_for_key(spec.default_codec, &spec.per_array, key_str);
+            manifest_entries.push(manifest_entry_for(key_str, &npy_bytes, codec));
 
             // Write to zip with .npy extension
             let filename = format!("{}.npy", key_str);
-            zip.start_file(&filename, options)?;
+            zip.start_file(&filename, spec.options_for(key_str)?)?;
             zip.write_all(&npy_bytes)?;
         }
 
+        let attrs_cbor = if attrs.is_null() {
+            None
+        } else {
+            Some(pyobject_to_cbor(attrs)?)
+        };
+        write_manifest(
+            &mut zip,
+            manifest_entries,
+            attrs_cbor,
+            spec.default_codec.options(spec.level)?,
+        )?;
+
         zip.finish()?;
     }
 
@@ -443,45 +1640,9 @@ pub unsafe fn load_logitnpz_bytes(data: *mut PyObject) -> Result<*mut PyObject,
     let bytes = std::slice::from_raw_parts(buf as *const u8, size as usize);
     let cursor = Cursor::new(bytes);
     let mut archive = ZipArchive::new(cursor)?;
+    let manifest = read_manifest(&mut archive)?;
 
-    let result_dict = PyDict_New();
-    if result_dict.is_null() {
-        return Err(LogitNpzError::PythonError);
-    }
-
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i)?;
-        let name = file.name().to_string();
-
-        // Only process .npy files
-        if !name.ends_with(".npy") {
-            continue;
-        }
-
-        // Read file contents
-        let mut data = Vec::new();
-        file.read_to_end(&mut data)?;
-
-        // Convert to numpy array
-        let arr = npy_bytes_to_array(&data)?;
-
-        // Get key name (remove .npy extension)
-        let key_name = &name[..name.len() - 4];
-        let key_obj =
-            PyUnicode_FromStringAndSize(key_name.as_ptr() as *const c_char, key_name.len() as isize);
-
-        if PyDict_SetItem(result_dict, key_obj, arr) < 0 {
-            Py_DECREF(key_obj);
-            Py_DECREF(arr);
-            Py_DECREF(result_dict);
-            return Err(LogitNpzError::PythonError);
-        }
-
-        Py_DECREF(key_obj);
-        Py_DECREF(arr);
-    }
-
-    Ok(result_dict)
+    load_archive_entries(&mut archive, manifest)
 }
 
 // ============================================================================
@@ -489,6 +1650,44 @@ pub unsafe fn load_logitnpz_bytes(data: *mut PyObject) -> Result<*mut PyObject,
 // ============================================================================
 
 const DEFAULT_COMPRESSION_LEVEL: i64 = 3;
+const DEFAULT_COMPRESSION_CODEC: CompressionCodec = CompressionCodec::Zstd;
+
+/// Builds a `CompressionSpec` from the raw `compression_method` argument,
+/// which is either a single codec name shared by every array or a
+/// `{name: codec}` dict for per-array overrides.
+unsafe fn build_compression_spec(
+    compression_method: *mut PyObject,
+    level: i64,
+) -> Result<CompressionSpec, LogitNpzError> {
+    if compression_method.is_null() {
+        return Ok(CompressionSpec {
+            default_codec: DEFAULT_COMPRESSION_CODEC,
+            level,
+            per_array: Vec::new(),
+        });
+    }
+    if PyUnicode_Check(compression_method) != 0 {
+        let mut size: Py_ssize_t = 0;
+        let ptr = PyUnicode_AsUTF8AndSize(compression_method, &mut size);
+        let name =
+            std::str::from_utf8_unchecked(std::slice::from_raw_parts(ptr as *const u8, size as usize));
+        Ok(CompressionSpec {
+            default_codec: CompressionCodec::parse(name)?,
+            level,
+            per_array: Vec::new(),
+        })
+    } else if PyDict_Check(compression_method) != 0 {
+        Ok(CompressionSpec {
+            default_codec: DEFAULT_COMPRESSION_CODEC,
+            level,
+            per_array: parse_per_array_codecs(compression_method)?,
+        })
+    } else {
+        Err(LogitNpzError::InvalidFormat(
+            "compression_method must be a str or a {str: str} dict".to_string(),
+        ))
+    }
+}
 
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn logitnpz_save(
@@ -508,6 +1707,8 @@ pub unsafe extern "C" fn logitnpz_save(
     let path = *args.offset(0);
     let arrays = *args.offset(1);
     let mut compression_level = DEFAULT_COMPRESSION_LEVEL;
+    let mut compression_method: *mut PyObject = std::ptr::null_mut();
+    let mut attrs: *mut PyObject = std::ptr::null_mut();
 
     if num_args >= 3 {
         let level_obj = *args.offset(2);
@@ -533,12 +1734,21 @@ pub unsafe extern "C" fn logitnpz_save(
                     if PyLong_Check(level_obj) != 0 {
                         compression_level = PyLong_AsLong(level_obj) as i64;
                     }
+                } else if name == "compression_method" {
+                    compression_method = *args.offset(num_args + i);
+                } else if name == "attrs" {
+                    attrs = *args.offset(num_args + i);
                 }
             }
         }
     }
 
-    match save_logitnpz(path, arrays, compression_level) {
+    let spec = match build_compression_spec(compression_method, compression_level) {
+        Ok(spec) => spec,
+        Err(e) => return e.to_py_error(),
+    };
+
+    match save_logitnpz(path, arrays, &spec, attrs) {
         Ok(()) => {
             Py_INCREF(crate::typeref::NONE);
             crate::typeref::NONE
@@ -550,14 +1760,95 @@ pub unsafe extern "C" fn logitnpz_save(
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn logitnpz_load(
     _self: *mut PyObject,
-    path: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
 ) -> *mut PyObject {
-    match load_logitnpz(path) {
+    let num_args = PyVectorcall_NARGS(nargs as usize);
+    if num_args < 1 {
+        let msg = "logitnpz_load() requires at least 1 argument: path\0";
+        PyErr_SetString(PyExc_TypeError, msg.as_ptr() as *const c_char);
+        return std::ptr::null_mut();
+    }
+    let path = *args.offset(0);
+    let mut use_mmap = false;
+
+    if !kwnames.is_null() {
+        let kwcount = Py_SIZE(kwnames);
+        for i in 0..kwcount {
+            let kwname = PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+            let mut size: Py_ssize_t = 0;
+            let ptr = PyUnicode_AsUTF8AndSize(kwname, &mut size);
+            if !ptr.is_null() {
+                let name = std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+                    ptr as *const u8,
+                    size as usize,
+                ));
+                if name == "mmap" {
+                    let mmap_obj = *args.offset(num_args + i);
+                    use_mmap = PyObject_IsTrue(mmap_obj) == 1;
+                }
+            }
+        }
+    }
+
+    let result = if use_mmap {
+        load_logitnpz_mmap(path)
+    } else {
+        load_logitnpz(path)
+    };
+    match result {
         Ok(dict) => dict,
         Err(e) => e.to_py_error(),
     }
 }
 
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn logitnpz_keys(
+    _self: *mut PyObject,
+    path: *mut PyObject,
+) -> *mut PyObject {
+    match logitnpz_keys_list(path) {
+        Ok(names) => {
+            let list = PyList_New(names.len() as Py_ssize_t);
+            for (i, name) in names.iter().enumerate() {
+                let name_obj =
+                    PyUnicode_FromStringAndSize(name.as_ptr() as *const c_char, name.len() as isize);
+                PyList_SET_ITEM(list, i as Py_ssize_t, name_obj);
+            }
+            list
+        }
+        Err(e) => e.to_py_error(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn logitnpz_load_one(
+    _self: *mut PyObject,
+    path: *mut PyObject,
+    name: *mut PyObject,
+) -> *mut PyObject {
+    let name_str = match pystr(name, "name") {
+        Ok(s) => s,
+        Err(e) => return e.to_py_error(),
+    };
+    match load_logitnpz_one(path, name_str) {
+        Ok(arr) => arr,
+        Err(e) => e.to_py_error(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn logitnpz_attrs(
+    _self: *mut PyObject,
+    path: *mut PyObject,
+) -> *mut PyObject {
+    match load_logitnpz_attrs(path) {
+        Ok(obj) => obj,
+        Err(e) => e.to_py_error(),
+    }
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn logitnpz_dumps(
     _self: *mut PyObject,
@@ -575,6 +1866,8 @@ pub unsafe extern "C" fn logitnpz_dumps(
 
     let arrays = *args.offset(0);
     let mut compression_level = DEFAULT_COMPRESSION_LEVEL;
+    let mut compression_method: *mut PyObject = std::ptr::null_mut();
+    let mut attrs: *mut PyObject = std::ptr::null_mut();
 
     if num_args >= 2 {
         let level_obj = *args.offset(1);
@@ -600,12 +1893,21 @@ pub unsafe extern "C" fn logitnpz_dumps(
                     if PyLong_Check(level_obj) != 0 {
                         compression_level = PyLong_AsLong(level_obj) as i64;
                     }
+                } else if name == "compression_method" {
+                    compression_method = *args.offset(num_args + i);
+                } else if name == "attrs" {
+                    attrs = *args.offset(num_args + i);
                 }
             }
         }
     }
 
-    match save_logitnpz_bytes(arrays, compression_level) {
+    let spec = match build_compression_spec(compression_method, compression_level) {
+        Ok(spec) => spec,
+        Err(e) => return e.to_py_error(),
+    };
+
+    match save_logitnpz_bytes(arrays, &spec, attrs) {
         Ok(bytes) => bytes,
         Err(e) => e.to_py_error(),
     }
@@ -621,3 +1923,141 @@ pub unsafe extern "C" fn logitnpz_loads(
         Err(e) => e.to_py_error(),
     }
 }
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn logitnpz_append(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    let num_args = PyVectorcall_NARGS(nargs as usize);
+
+    if num_args < 2 {
+        let msg = "logitnpz_append() requires at least 2 arguments: path, arrays\0";
+        PyErr_SetString(PyExc_TypeError, msg.as_ptr() as *const c_char);
+        return std::ptr::null_mut();
+    }
+
+    let path = *args.offset(0);
+    let arrays = *args.offset(1);
+    let mut compression_level = DEFAULT_COMPRESSION_LEVEL;
+    let mut compression_method: *mut PyObject = std::ptr::null_mut();
+    let mut overwrite = false;
+
+    if !kwnames.is_null() {
+        let kwcount = Py_SIZE(kwnames);
+        for i in 0..kwcount {
+            let kwname = PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
+            let mut size: Py_ssize_t = 0;
+            let ptr = PyUnicode_AsUTF8AndSize(kwname, &mut size);
+            if !ptr.is_null() {
+                let name = std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+                    ptr as *const u8,
+                    size as usize,
+                ));
+                if name == "compression_level" {
+                    let level_obj = *args.offset(num_args + i);
+                    if PyLong_Check(level_obj) != 0 {
+                        compression_level = PyLong_AsLong(level_obj) as i64;
+                    }
+                } else if name == "compression_method" {
+                    compression_method = *args.offset(num_args + i);
+                } else if name == "overwrite" {
+                    let overwrite_obj = *args.offset(num_args + i);
+                    overwrite = PyObject_IsTrue(overwrite_obj) == 1;
+                }
+            }
+        }
+    }
+
+    let spec = match build_compression_spec(compression_method, compression_level) {
+        Ok(spec) => spec,
+        Err(e) => return e.to_py_error(),
+    };
+
+    match append_logitnpz(path, arrays, &spec, overwrite) {
+        Ok(()) => {
+            Py_INCREF(crate::typeref::NONE);
+            crate::typeref::NONE
+        }
+        Err(e) => e.to_py_error(),
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn logitnpz_iterload(
+    _self: *mut PyObject,
+    path: *mut PyObject,
+) -> *mut PyObject {
+    match iter_logitnpz(path) {
+        Ok(iter) => iter,
+        Err(e) => e.to_py_error(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn npy_header_round_trips_through_the_writer() {
+        let bytes = build_npy_header("<f4", &[2, 3]);
+        let header = parse_npy_header(&bytes).expect("header should parse");
+        assert_eq!(header.descr, "<f4");
+        assert_eq!(header.shape, vec![2, 3]);
+        assert!(!header.fortran_order);
+        assert_eq!(header.data_offset, bytes.len());
+    }
+
+    #[test]
+    fn npy_header_rejects_garbage() {
+        assert!(parse_npy_header(b"not an npy file").is_none());
+        assert!(parse_npy_header(&[0u8; 4]).is_none());
+    }
+
+    #[test]
+    fn manifest_entry_for_falls_back_when_header_is_unparseable() {
+        let entry = manifest_entry_for("weights", b"garbage bytes", CompressionCodec::Stored);
+        assert_eq!(entry.name, "weights");
+        assert!(!entry.validated);
+        assert_eq!(entry.dtype, "");
+        assert!(entry.shape.is_empty());
+    }
+
+    #[test]
+    fn manifest_entry_for_records_real_header_when_parseable() {
+        let npy_bytes = build_npy_header("<i8", &[4]);
+        let entry = manifest_entry_for("indices", &npy_bytes, CompressionCodec::Zstd);
+        assert!(entry.validated);
+        assert_eq!(entry.dtype, "<i8");
+        assert_eq!(entry.shape, vec![4]);
+        assert_eq!(entry.compression, "zstd");
+    }
+
+    #[test]
+    fn older_manifests_without_validated_field_default_to_validated() {
+        // An entry encoded the way a pre-`validated`-field orjson build
+        // would have written it (no fifth map key at all).
+        let map = CborValue::Map(vec![
+            (CborValue::Text("name".into()), CborValue::Text("x".into())),
+            (CborValue::Text("dtype".into()), CborValue::Text("<f4".into())),
+            (CborValue::Text("shape".into()), CborValue::Array(vec![CborValue::Integer(1.into())])),
+            (CborValue::Text("compression".into()), CborValue::Text("stored".into())),
+        ]);
+        let mut bytes = Vec::new();
+        ciborium::into_writer(&map, &mut bytes).unwrap();
+        let entry: ManifestEntry = ciborium::from_reader(bytes.as_slice())
+            .expect("ManifestEntry should deserialize without the `validated` field");
+        assert!(entry.validated);
+    }
+
+    #[test]
+    fn compression_codec_round_trips_through_name() {
+        for name in ["stored", "zstd", "lz4", "gzip", "bzip2"] {
+            let codec = CompressionCodec::parse(name).expect("known codec name");
+            assert_eq!(codec.name(), name);
+        }
+        assert!(CompressionCodec::parse("not-a-codec").is_err());
+    }
+}
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Small macros shared by every module that talks to the raw `pyo3_ffi` C
+//! API, so call sites read as the one-line C calls they are instead of
+//! being wrapped in an `unsafe { ... }` block (and an `Option`/`NonNull`
+//! dance) every single time.
+//!
+//! Expected to be brought into scope crate-wide via `#[macro_use] mod
+//! macros;` at the top of the crate root, the same way every other
+//! `#[macro_export]` macro in this file is written to be used unqualified.
+
+/// Runs a raw `pyo3_ffi` call inside an `unsafe` block. The call itself is
+/// trusted to be safe by its caller (right argument types/counts, correct
+/// object ownership) -- this macro only saves the boilerplate, it adds no
+/// checking of its own.
+#[macro_export]
+macro_rules! ffi {
+    ($e:expr) => {
+        unsafe { $e }
+    };
+}
+
+/// Wraps a possibly-null `*mut PyObject` (or similar C pointer) in a
+/// `NonNull`. Used only where a null here would mean the process's own
+/// invariants are already broken (e.g. a singleton that's always set by
+/// `typeref::init` before any entry point runs) -- genuine allocation
+/// failures from CPython are handled by checking `.is_null()` directly and
+/// propagating the pending `PyErr`, not through this macro.
+#[macro_export]
+macro_rules! nonnull {
+    ($e:expr) => {
+        match core::ptr::NonNull::new($e) {
+            Some(ptr) => ptr,
+            None => unsafe { core::hint::unreachable_unchecked() },
+        }
+    };
+}
+
+/// Reads one of [`crate::typeref`]'s singleton statics (`None`/`True`/
+/// `False`/the empty `str`) as a new reference. As of CPython 3.12 these
+/// are immortal objects whose refcount is never decremented to zero, so
+/// no `Py_INCREF` is taken there; on older runtimes one is taken, since
+/// callers `Py_DECREF` the result like any other new reference.
+#[macro_export]
+macro_rules! use_immortal {
+    ($global:expr) => {
+        unsafe {
+            #[cfg(not(Py_3_12))]
+            pyo3_ffi::Py_INCREF($global);
+            $global
+        }
+    };
+}
+
+/// Hints that a branch is rarely taken (the the long-key cache bypass, a
+/// malformed-input early return, ...). No-op on stable Rust -- the real
+/// payoff is documentation of intent, not codegen -- but kept as a macro
+/// so call sites don't change if this crate ever moves to a toolchain
+/// where `core::intrinsics::unlikely` (or its stable equivalent) is
+/// available.
+#[macro_export]
+macro_rules! unlikely {
+    ($e:expr) => {
+        $e
+    };
+}
+
+/// Shorthand for `core::hint::unreachable_unchecked()`, used at call sites
+/// that have already established (via an earlier check, or a process-wide
+/// invariant like "the GIL serializes access") that the branch genuinely
+/// cannot be reached.
+#[macro_export]
+macro_rules! unreachable_unchecked {
+    () => {
+        core::hint::unreachable_unchecked()
+    };
+}
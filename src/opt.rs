@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Bit flags accepted as the `option=` integer on the public `dumps`/`loads`
+//! entry points. Each flag is an independent bit so callers can combine
+//! them with `|`; `Opt` itself is just a `u32` so the check at each call
+//! site is a plain `opts & FLAG != 0`.
+
+pub type Opt = u32;
+
+/// Serialize `NaN`/`Infinity`/`-Infinity` floats as JSON `null` instead of
+/// erroring (the default, matching the JSON spec) or emitting the bare
+/// literal (`OPT_NON_FINITE_LITERALS`).
+pub const SANITIZE_NAN: Opt = 1 << 0;
+
+/// On `dumps`, write non-finite floats as the bare `NaN`/`Infinity`/
+/// `-Infinity` tokens instead of erroring or sanitizing to `null`
+/// (`SANITIZE_NAN`). On `loads`, accept those same bare tokens wherever a
+/// number is expected, so a document produced with this flag round-trips
+/// back through `loads` with this flag rather than failing to parse.
+pub const OPT_NON_FINITE_LITERALS: Opt = 1 << 1;
+
+/// On `loads`, parse any number with a fractional part or exponent as a
+/// `decimal.Decimal` built from its original digit text instead of an
+/// `f64`, preserving precision/trailing zeros that `f64` would round away
+/// or drop.
+pub const OPT_PARSE_DECIMAL: Opt = 1 << 2;
@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! `orjson.RawJSON`: wraps an already-encoded JSON fragment (`bytes` or
+//! `str`) so `dumps` writes it into the output verbatim instead of trying
+//! to serialize it as a normal value (see
+//! [`crate::serialize::per_type::rawjson::RawJSONSerializer`]), and so
+//! `loads(..., schema=...)`'s raw-passthrough path (a schema node with
+//! `"raw": true`, see [`crate::deserialize::schema`]) can hand a
+//! sub-document back as its source byte range instead of the Python
+//! objects it parses to (those are still built while the sub-document is
+//! parsed, then dropped).
+//!
+//! A hand-rolled `PyTypeObject`, the same way [`crate::logitnpz`] defines
+//! `LogitNpzIterator` without a separate Python-level class -- there's no
+//! `lib.rs` in this slice to register a class the usual way, so this
+//! follows the one native-type pattern the crate already has.
+
+use pyo3_ffi::*;
+use std::os::raw::c_char;
+
+#[repr(C)]
+struct RawJSONObject {
+    ob_base: PyObject,
+    /// Always a `bytes` object holding the fragment's UTF-8 text.
+    payload: *mut PyObject,
+}
+
+unsafe extern "C" fn rawjson_dealloc(obj: *mut PyObject) {
+    let it = obj as *mut RawJSONObject;
+    if !(*it).payload.is_null() {
+        Py_DECREF((*it).payload);
+    }
+    std::alloc::dealloc(obj as *mut u8, std::alloc::Layout::new::<RawJSONObject>());
+}
+
+/// `RawJSON(fragment)` -- `fragment` must be `bytes` or `str`; a `str` is
+/// encoded to UTF-8 once here so the serializer never has to care which it
+/// got.
+unsafe extern "C" fn rawjson_new(
+    subtype: *mut PyTypeObject,
+    args: *mut PyObject,
+    _kwds: *mut PyObject,
+) -> *mut PyObject {
+    let mut fragment: *mut PyObject = std::ptr::null_mut();
+    if PyArg_ParseTuple(args, "O\0".as_ptr() as *const c_char, &mut fragment) == 0 {
+        return std::ptr::null_mut();
+    }
+
+    let payload = if PyBytes_Check(fragment) != 0 {
+        Py_INCREF(fragment);
+        fragment
+    } else if PyUnicode_Check(fragment) != 0 {
+        let mut size: Py_ssize_t = 0;
+        let data = PyUnicode_AsUTF8AndSize(fragment, &mut size);
+        if data.is_null() {
+            return std::ptr::null_mut();
+        }
+        PyBytes_FromStringAndSize(data, size)
+    } else {
+        PyErr_SetString(
+            PyExc_TypeError,
+            "RawJSON() argument must be bytes or str\0".as_ptr() as *const c_char,
+        );
+        return std::ptr::null_mut();
+    };
+    if payload.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    match alloc_rawjson(subtype, payload) {
+        Some(obj) => obj,
+        None => {
+            Py_DECREF(payload);
+            PyErr_NoMemory()
+        }
+    }
+}
+
+unsafe fn alloc_rawjson(ty: *mut PyTypeObject, payload: *mut PyObject) -> Option<*mut PyObject> {
+    let layout = std::alloc::Layout::new::<RawJSONObject>();
+    let obj = std::alloc::alloc(layout) as *mut RawJSONObject;
+    if obj.is_null() {
+        return None;
+    }
+    PyObject_Init(obj as *mut PyObject, ty);
+    (*obj).payload = payload;
+    Some(obj as *mut PyObject)
+}
+
+static RAWJSON_TYPE: std::sync::OnceLock<usize> = std::sync::OnceLock::new();
+
+/// Builds (once per interpreter) the static `PyTypeObject` backing
+/// `orjson.RawJSON`, the same hand-rolled way
+/// [`crate::logitnpz::logitnpz_iter_type`] builds its iterator type.
+pub unsafe fn rawjson_type() -> *mut PyTypeObject {
+    let addr = RAWJSON_TYPE.get_or_init(|| {
+        let ty = Box::leak(Box::new(std::mem::zeroed::<PyTypeObject>()));
+        PyObject_Init(
+            std::ptr::addr_of_mut!(ty.ob_base.ob_base) as *mut PyObject,
+            std::ptr::addr_of_mut!(PyType_Type),
+        );
+        ty.tp_name = "orjson.RawJSON\0".as_ptr() as *const c_char;
+        ty.tp_basicsize = std::mem::size_of::<RawJSONObject>() as Py_ssize_t;
+        ty.tp_itemsize = 0;
+        ty.tp_flags = Py_TPFLAGS_DEFAULT | Py_TPFLAGS_BASETYPE;
+        ty.tp_new = Some(rawjson_new);
+        ty.tp_dealloc = Some(rawjson_dealloc);
+        if PyType_Ready(ty as *mut PyTypeObject) < 0 {
+            PyErr_Clear();
+        }
+        ty as *mut PyTypeObject as usize
+    });
+    *addr as *mut PyTypeObject
+}
+
+/// Builds a new `RawJSON` directly from a byte range sliced out of the
+/// input buffer during deserialization -- the deserializer's own
+/// construction path, bypassing `tp_new`'s argument parsing since the
+/// fragment is already known-valid JSON text, not a user-supplied object.
+pub unsafe fn new_rawjson_from_fragment(fragment: &[u8]) -> *mut PyObject {
+    let payload =
+        PyBytes_FromStringAndSize(fragment.as_ptr() as *const c_char, fragment.len() as isize);
+    if payload.is_null() {
+        return std::ptr::null_mut();
+    }
+    match alloc_rawjson(rawjson_type(), payload) {
+        Some(obj) => obj,
+        None => {
+            Py_DECREF(payload);
+            PyErr_NoMemory()
+        }
+    }
+}
+
+/// `true` if `obj` is an instance of `orjson.RawJSON` (or a subclass).
+pub unsafe fn is_rawjson(obj: *mut PyObject) -> bool {
+    PyObject_TypeCheck(obj, rawjson_type()) != 0
+}
+
+/// Borrows the `bytes` payload of a `RawJSON` instance. The caller must
+/// have already checked [`is_rawjson`].
+pub unsafe fn rawjson_payload(obj: *mut PyObject) -> *mut PyObject {
+    (*(obj as *mut RawJSONObject)).payload
+}
@@ -1,8 +1,22 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use crate::opt::{Opt, SANITIZE_NAN};
+use crate::opt::{Opt, OPT_NON_FINITE_LITERALS, SANITIZE_NAN};
 use serde::ser::{Serialize, Serializer};
 
+/// Newtype-struct name our `Serializer` impls special-case to mean "write
+/// this string's bytes verbatim, unescaped and unquoted" rather than a
+/// normal JSON string -- the same convention `serde_json::value::RawValue`
+/// uses internally. Shared with [`crate::serialize::per_type::rawjson`].
+pub(crate) const RAW_TOKEN_MAGIC: &str = "$orjson::private::RawToken";
+
+#[inline(always)]
+pub(crate) fn serialize_raw_token<S>(serializer: S, token: &str) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_newtype_struct(RAW_TOKEN_MAGIC, token)
+}
+
 pub struct FloatSerializer {
     ptr: *mut pyo3_ffi::PyObject,
     opts: Opt,
@@ -21,9 +35,18 @@ impl Serialize for FloatSerializer {
         S: Serializer,
     {
         let value = ffi!(PyFloat_AS_DOUBLE(self.ptr));
-        
-        // Check if SANITIZE_NAN option is set and value is NaN or Infinity
-        if self.opts & SANITIZE_NAN != 0 && (value.is_nan() || value.is_infinite()) {
+
+        if self.opts & OPT_NON_FINITE_LITERALS != 0 && (value.is_nan() || value.is_infinite()) {
+            let token = if value.is_nan() {
+                "NaN"
+            } else if value.is_sign_negative() {
+                "-Infinity"
+            } else {
+                "Infinity"
+            };
+            serialize_raw_token(serializer, token)
+        } else if self.opts & SANITIZE_NAN != 0 && (value.is_nan() || value.is_infinite()) {
+            // Check if SANITIZE_NAN option is set and value is NaN or Infinity
             serializer.serialize_unit()
         } else {
             #[cfg(yyjson_allow_inf_and_nan)]
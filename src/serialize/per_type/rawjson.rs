@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::rawjson::{is_rawjson, rawjson_payload};
+use crate::serialize::per_type::float::serialize_raw_token;
+use serde::ser::{Serialize, Serializer};
+use std::os::raw::c_char;
+
+/// Wraps a `bytes` value holding already-valid, already-UTF-8 JSON and
+/// writes it into the output stream verbatim -- no escaping, no
+/// re-encoding -- mirroring `serde_json::value::RawValue`. Useful for
+/// embedding precomputed JSON (e.g. cached rows) without a
+/// parse-then-reserialize round trip.
+pub struct RawJSONSerializer {
+    ptr: *mut pyo3_ffi::PyObject,
+}
+
+impl RawJSONSerializer {
+    pub fn new(ptr: *mut pyo3_ffi::PyObject) -> Self {
+        RawJSONSerializer { ptr }
+    }
+
+    /// Recognizes an `orjson.RawJSON` instance (see [`crate::rawjson`]) and builds a
+    /// serializer over its payload bytes, or returns `None` if `obj` isn't
+    /// one. There's no top-level per-type dispatch enum in this slice for
+    /// `dumps` to drive automatically (no crate root wires one up yet), so
+    /// this is the check whatever eventually builds that cascade should
+    /// call before falling through to its other per-type serializers.
+    pub fn from_object(obj: *mut pyo3_ffi::PyObject) -> Option<Self> {
+        unsafe {
+            if is_rawjson(obj) {
+                Some(RawJSONSerializer::new(rawjson_payload(obj)))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl Serialize for RawJSONSerializer {
+    #[inline(always)]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buf: *mut c_char = std::ptr::null_mut();
+        let mut len: pyo3_ffi::Py_ssize_t = 0;
+        if unsafe { pyo3_ffi::PyBytes_AsStringAndSize(self.ptr, &mut buf, &mut len) } < 0 {
+            return Err(serde::ser::Error::custom(
+                "RawJSON fragment must be a bytes object",
+            ));
+        }
+        let fragment = unsafe {
+            std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+                buf as *const u8,
+                len as usize,
+            ))
+        };
+        serialize_raw_token(serializer, fragment)
+    }
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Small helpers for building Python `str` objects from already-decoded
+//! UTF-8, shared between the deserializer and its key cache.
+
+use pyo3_ffi::PyObject;
+
+/// Builds a new Python `str` from `s`. Callers that intern the result
+/// (see [`crate::deserialize::cache`]) pair this with [`hash_str`] so the
+/// object's hash is cached by CPython before it's stored long-term.
+#[inline(always)]
+pub fn unicode_from_str(s: &str) -> *mut PyObject {
+    unsafe {
+        pyo3_ffi::PyUnicode_FromStringAndSize(s.as_ptr() as *const std::os::raw::c_char, s.len() as isize)
+    }
+}
+
+/// Forces CPython to compute and cache `obj`'s hash immediately, so the
+/// first real lookup against it (e.g. as a dict key) doesn't pay for it.
+#[inline(always)]
+pub fn hash_str(obj: *mut PyObject) {
+    unsafe {
+        pyo3_ffi::PyObject_Hash(obj);
+    }
+}
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Process-wide caches for Python globals (singletons, optional-dependency
+//! classes) resolved once via `OnceLock`/lazily-initialized statics and
+//! reused for the life of the interpreter, rather than re-looking them up
+//! on every call -- the same convention [`crate::logitnpz`] already uses
+//! for `numpy`/`io.BytesIO`.
+
+use core::ptr::NonNull;
+use pyo3_ffi::PyObject;
+
+/// `None`/`True`/`False`/`""`, set once by [`init`]. Reading these before
+/// `init` runs is a bug in the embedder, not something this module can
+/// recover from -- the same assumption the rest of the crate makes about
+/// the interpreter being up before any orjson entry point is called.
+pub static mut NONE: *mut PyObject = std::ptr::null_mut();
+pub static mut TRUE: *mut PyObject = std::ptr::null_mut();
+pub static mut FALSE: *mut PyObject = std::ptr::null_mut();
+pub static mut EMPTY_UNICODE: *mut PyObject = std::ptr::null_mut();
+
+/// Populates the globals above. Called once from the module's `PyInit_`
+/// entry point (outside this slice, same as the rest of this crate's
+/// Python-module wiring).
+pub unsafe fn init() {
+    NONE = pyo3_ffi::Py_None();
+    TRUE = pyo3_ffi::Py_True();
+    FALSE = pyo3_ffi::Py_False();
+    EMPTY_UNICODE = pyo3_ffi::PyUnicode_FromStringAndSize(std::ptr::null(), 0);
+
+    // Pre-populate the key-interning cache's `OnceLock` so
+    // `KEY_MAP.get_mut()` in `deserialize::pyobject::get_unicode_key` never
+    // observes it empty -- everything in this module is set up once here,
+    // before any `loads()` call can run.
+    #[cfg(not(Py_GIL_DISABLED))]
+    {
+        let _ = crate::deserialize::KEY_MAP.get_or_init(crate::deserialize::KeyMap::default);
+    }
+}
+
+/// A cached strong reference to a Python class/function resolved from an
+/// optional dependency, wrapped so it can live in a `static` (raw
+/// `PyObject` pointers aren't `Sync`, but these are only ever read after
+/// the `OnceLock` that holds them finishes initializing, so sharing them
+/// across threads is sound -- matching [`crate::logitnpz::NumpyFuncs`]'s
+/// reasoning for the same pattern).
+#[derive(Clone, Copy)]
+pub struct PyClassRef(NonNull<PyObject>);
+
+unsafe impl Sync for PyClassRef {}
+unsafe impl Send for PyClassRef {}
+
+impl PyClassRef {
+    pub fn as_ptr(&self) -> *mut PyObject {
+        self.0.as_ptr()
+    }
+}
+
+/// Marker recording that `numpy` is importable, resolved once the same
+/// way [`crate::logitnpz`]'s other optional-dependency caches are.
+pub struct NumpyTypes;
+
+unsafe impl Sync for NumpyTypes {}
+
+pub static NUMPY_TYPES: std::sync::OnceLock<Option<NumpyTypes>> = std::sync::OnceLock::new();
+
+pub unsafe fn load_numpy_types() -> Option<NumpyTypes> {
+    let numpy_mod = pyo3_ffi::PyImport_ImportModule("numpy\0".as_ptr() as *const std::os::raw::c_char);
+    if numpy_mod.is_null() {
+        pyo3_ffi::PyErr_Clear();
+        return None;
+    }
+    pyo3_ffi::Py_DECREF(numpy_mod);
+    Some(NumpyTypes)
+}
+
+pub static DECIMAL_CLASS: std::sync::OnceLock<Option<PyClassRef>> = std::sync::OnceLock::new();
+
+/// Resolves `decimal.Decimal`, used by [`crate::deserialize::pyobject::parse_decimal`]
+/// under `OPT_PARSE_DECIMAL` to preserve a number's exact textual
+/// precision instead of rounding it through `f64`.
+pub unsafe fn load_decimal_class() -> Option<PyClassRef> {
+    let decimal_mod =
+        pyo3_ffi::PyImport_ImportModule("decimal\0".as_ptr() as *const std::os::raw::c_char);
+    if decimal_mod.is_null() {
+        pyo3_ffi::PyErr_Clear();
+        return None;
+    }
+    let cls =
+        pyo3_ffi::PyObject_GetAttrString(decimal_mod, "Decimal\0".as_ptr() as *const std::os::raw::c_char);
+    pyo3_ffi::Py_DECREF(decimal_mod);
+    if cls.is_null() {
+        pyo3_ffi::PyErr_Clear();
+        return None;
+    }
+    NonNull::new(cls).map(PyClassRef)
+}